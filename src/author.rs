@@ -0,0 +1,191 @@
+use crate::config::{Config, PLACEHOLDER_AUTHOR};
+use std::fmt;
+use std::process::Command;
+
+/// Where the author written to Cargo.toml came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorSource {
+    Flag,
+    Config,
+    CargoEnv,
+    GitConfig,
+    UserEnv,
+    Placeholder,
+}
+
+impl fmt::Display for AuthorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            Self::Flag => "--author",
+            Self::Config => "default-author in config",
+            Self::CargoEnv => "CARGO_NAME/CARGO_EMAIL",
+            Self::GitConfig => "git config user.name/user.email",
+            Self::UserEnv => "$USER",
+            Self::Placeholder => "placeholder",
+        };
+        write!(f, "{}", source)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Author {
+    pub value: String,
+    pub source: AuthorSource,
+}
+
+/// Resolve the author: --author, then an explicit default-author, then auto-detection,
+/// and only then the placeholder
+pub fn resolve(flag: Option<&str>, config: &Config) -> Author {
+    resolve_with(flag, config, |name| std::env::var(name).ok(), git_config)
+}
+
+fn resolve_with(
+    flag: Option<&str>,
+    config: &Config,
+    env: impl Fn(&str) -> Option<String>,
+    git: impl Fn(&str) -> Option<String>,
+) -> Author {
+    let author = |value: String, source| Author { value, source };
+
+    if let Some(value) = flag {
+        return author(value.to_string(), AuthorSource::Flag);
+    }
+
+    if config.default_author != PLACEHOLDER_AUTHOR {
+        return author(config.default_author.clone(), AuthorSource::Config);
+    }
+
+    if let Some(value) = format_author(env("CARGO_NAME"), env("CARGO_EMAIL")) {
+        return author(value, AuthorSource::CargoEnv);
+    }
+
+    if let Some(value) = format_author(git("user.name"), git("user.email")) {
+        return author(value, AuthorSource::GitConfig);
+    }
+
+    if let Some(value) = format_author(env("USER"), None) {
+        return author(value, AuthorSource::UserEnv);
+    }
+
+    author(PLACEHOLDER_AUTHOR.to_string(), AuthorSource::Placeholder)
+}
+
+/// `Name <email>`, or just `Name` without an email; None without a name
+fn format_author(name: Option<String>, email: Option<String>) -> Option<String> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())?;
+
+    match email.map(|e| e.trim().to_string()).filter(|e| !e.is_empty()) {
+        Some(email) => Some(format!("{} <{}>", name, email)),
+        None => Some(name),
+    }
+}
+
+fn git_config(key: &str) -> Option<String> {
+    let output = Command::new("git").args(["config", "--get", key]).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| map.get(key).cloned()
+    }
+
+    #[test]
+    fn test_resolve_prefers_flag() {
+        let config = Config::default();
+
+        let author = resolve_with(
+            Some("Flag Author"),
+            &config,
+            lookup(&[("CARGO_NAME", "Cargo")]),
+            lookup(&[]),
+        );
+
+        assert_eq!(author.value, "Flag Author");
+        assert_eq!(author.source, AuthorSource::Flag);
+    }
+
+    #[test]
+    fn test_resolve_uses_explicit_config_author() {
+        let config = Config {
+            default_author: "Configured <c@example.com>".to_string(),
+            ..Config::default()
+        };
+
+        let author = resolve_with(None, &config, lookup(&[("CARGO_NAME", "Cargo")]), lookup(&[]));
+
+        assert_eq!(author.value, "Configured <c@example.com>");
+        assert_eq!(author.source, AuthorSource::Config);
+    }
+
+    #[test]
+    fn test_resolve_cargo_env_before_git() {
+        let config = Config::default();
+
+        let author = resolve_with(
+            None,
+            &config,
+            lookup(&[("CARGO_NAME", "Cargo Name"), ("CARGO_EMAIL", "cargo@example.com")]),
+            lookup(&[("user.name", "Git Name")]),
+        );
+
+        assert_eq!(author.value, "Cargo Name <cargo@example.com>");
+        assert_eq!(author.source, AuthorSource::CargoEnv);
+    }
+
+    #[test]
+    fn test_resolve_git_config() {
+        let config = Config::default();
+
+        let author = resolve_with(
+            None,
+            &config,
+            lookup(&[("USER", "login")]),
+            lookup(&[("user.name", "Git Name"), ("user.email", "git@example.com")]),
+        );
+
+        assert_eq!(author.value, "Git Name <git@example.com>");
+        assert_eq!(author.source, AuthorSource::GitConfig);
+    }
+
+    #[test]
+    fn test_resolve_user_env() {
+        let config = Config::default();
+
+        let author = resolve_with(None, &config, lookup(&[("USER", "login")]), lookup(&[]));
+
+        assert_eq!(author.value, "login");
+        assert_eq!(author.source, AuthorSource::UserEnv);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_placeholder() {
+        let config = Config::default();
+
+        let author = resolve_with(None, &config, lookup(&[]), lookup(&[]));
+
+        assert_eq!(author.value, PLACEHOLDER_AUTHOR);
+        assert_eq!(author.source, AuthorSource::Placeholder);
+    }
+
+    #[test]
+    fn test_format_author_requires_name() {
+        assert_eq!(format_author(None, Some("a@b.c".to_string())), None);
+        assert_eq!(format_author(Some("  ".to_string()), None), None);
+        assert_eq!(
+            format_author(Some("Name".to_string()), Some("".to_string())),
+            Some("Name".to_string())
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use clap::Parser;
+use crate::config::{Component, ConfigFormat, LoggingBackend, ProjectKind};
+use crate::conflict::ConflictPolicy;
+use clap::{Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, Shell};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -6,28 +9,122 @@ use std::path::PathBuf;
     name = "scaffold",
     about = "A Rust CLI project scaffolding tool that generates production-ready CLI applications",
     version = env!("GIT_DESCRIBE"),
-    after_help = "Logs are written to: ~/.local/share/scaffold/logs/scaffold.log\n\nThis tool generates complete Rust CLI projects with best practices including:\n- Proper error handling with eyre\n- Structured logging with env_logger\n- Configuration management with serde_yaml\n- Modern CLI parsing with clap\n- Git version integration"
+    after_help = "Logs are written to: ~/.local/share/scaffold/logs/scaffold.log\n\nThis tool generates complete Rust CLI projects with best practices including:\n- Proper error handling with eyre\n- Structured logging with env_logger\n- Configuration management with serde_yaml\n- Modern CLI parsing with clap\n- Git version integration",
+    args_conflicts_with_subcommands = true
 )]
 pub struct Cli {
-    /// Name of the project to create
-    pub project: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Name of the project to create; omit or use '.' to scaffold into the current directory
+    #[arg(
+        help = "Name of the project to create; omit or use '.' to scaffold into the current directory. A project named like a subcommand (config, completions, man, regen-cli) needs a path or '--': scaffold ./man or scaffold -- man"
+    )]
+    pub project: Option<String>,
 
     /// Author name for Cargo.toml
     #[arg(short, long, help = "Author name for Cargo.toml")]
     pub author: Option<String>,
 
-    /// Target directory (default: ./<project-name>)
-    #[arg(short, long, help = "Target directory (default: ./<project-name>)")]
+    /// Target directory (default: ./<project-name>, or the current directory without a name)
+    #[arg(
+        short,
+        long,
+        help = "Target directory (default: ./<project-name>, or the current directory without a name)"
+    )]
     pub directory: Option<PathBuf>,
 
     /// Path to config file
-    #[arg(short, long, help = "Path to config file")]
+    #[arg(short, long, global = true, help = "Path to config file")]
     pub config: Option<PathBuf>,
 
+    /// Profile from scaffold.yml to apply
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Profile from scaffold.yml to apply",
+        add = ArgValueCandidates::new(crate::completions::profile_candidates)
+    )]
+    pub profile: Option<String>,
+
+    /// Never run $(command) expansions from config files
+    #[arg(long, global = true, help = "Never run $(command) expansions from config files")]
+    pub no_command_expansion: bool,
+
+    /// Project layout to generate (overrides template.kind in scaffold.yml)
+    #[arg(
+        long,
+        value_enum,
+        help = "Project layout to generate (overrides template.kind in scaffold.yml)",
+        add = ArgValueCandidates::new(crate::completions::kind_candidates)
+    )]
+    pub kind: Option<ProjectKind>,
+
+    /// Split the generated project into a library and a thin binary
+    #[arg(
+        long,
+        help = "Split the generated project into a library and a thin binary (sets template.create-lib)"
+    )]
+    pub lib: bool,
+
+    /// Add hidden completions and man subcommands to the generated CLI
+    #[arg(
+        long,
+        help = "Add hidden completions and man subcommands to the generated CLI (sets template.create-completions)"
+    )]
+    pub completions: bool,
+
+    /// CLI spec to compile into the generated cli.rs and handlers
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "CLI spec to compile into the generated cli.rs and handlers (overrides template.cli.spec)"
+    )]
+    pub cli_spec: Option<PathBuf>,
+
+    /// Logging backend for the generated project
+    #[arg(
+        long,
+        value_enum,
+        help = "Logging backend for the generated project (overrides template.logging in scaffold.yml)"
+    )]
+    pub logging: Option<LoggingBackend>,
+
+    /// Config file format for the generated project
+    #[arg(
+        long,
+        value_enum,
+        help = "Config file format for the generated project (overrides template.config-format in scaffold.yml)"
+    )]
+    pub config_format: Option<ConfigFormat>,
+
+    /// Optional component to generate, repeatable
+    #[arg(
+        long = "component",
+        value_enum,
+        value_name = "COMPONENT",
+        help = "Optional component to generate, repeatable (adds to template.components in scaffold.yml)",
+        add = ArgValueCandidates::new(crate::completions::component_candidates)
+    )]
+    pub components: Vec<Component>,
+
+    /// Use an inferred project name without asking for confirmation
+    #[arg(short, long, help = "Use an inferred project name without asking for confirmation")]
+    pub yes: bool,
+
     /// Force scaffold even if directory contains non-repo files
-    #[arg(short, long, help = "Force scaffold even if directory contains non-repo files")]
+    #[arg(
+        short,
+        long,
+        help = "Force scaffold even if directory contains non-repo files (existing files are skipped unless --on-conflict is set)"
+    )]
     pub force: bool,
 
+    /// What to do with generated files that already exist
+    #[arg(long, value_enum, help = "What to do with generated files that already exist")]
+    pub on_conflict: Option<ConflictPolicy>,
+
     /// Don't initialize git repository
     #[arg(long, help = "Don't initialize git repository")]
     pub no_git: bool,
@@ -48,6 +145,57 @@ pub struct Cli {
     pub no_deps: bool,
 }
 
+#[derive(Subcommand)]
+pub enum Command {
+    /// Inspect scaffold configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Regenerate a project's cli.rs from its CLI spec, keeping handler code
+    RegenCli {
+        /// CLI spec to compile (default: cli-spec.yml in the project directory)
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "CLI spec to compile (default: cli-spec.yml in the project directory)"
+        )]
+        spec: Option<PathBuf>,
+
+        /// Project directory; a workspace root resolves to its crates/*-cli package
+        #[arg(
+            short,
+            long,
+            default_value = ".",
+            help = "Project directory; a workspace root resolves to its crates/*-cli package"
+        )]
+        directory: PathBuf,
+    },
+    /// Print the script that registers scaffold's shell completions
+    Completions {
+        /// Shell to register completions with
+        shell: Shell,
+    },
+    /// Print scaffold's man page
+    Man {
+        /// Write man pages for scaffold and each subcommand to this directory instead
+        #[arg(
+            short,
+            long,
+            help = "Write man pages for scaffold and each subcommand to this directory instead"
+        )]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the JSON Schema for scaffold.yml
+    Schema,
+    /// Print the effective config and where the author comes from
+    Show,
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -57,7 +205,7 @@ mod tests {
     #[test]
     fn test_cli_parses_project_name() {
         let cli = Cli::try_parse_from(["scaffold", "my-project"]).unwrap();
-        assert_eq!(cli.project, "my-project");
+        assert_eq!(cli.project.as_deref(), Some("my-project"));
         assert!(cli.author.is_none());
         assert!(cli.directory.is_none());
         assert!(cli.config.is_none());
@@ -78,6 +226,9 @@ mod tests {
             "/tmp/test",
             "--config",
             "config.yml",
+            "--profile",
+            "work",
+            "--no-command-expansion",
             "--no-git",
             "--no-sample-config",
             "--no-verify",
@@ -85,10 +236,12 @@ mod tests {
         ])
         .unwrap();
 
-        assert_eq!(cli.project, "test-project");
+        assert_eq!(cli.project.as_deref(), Some("test-project"));
         assert_eq!(cli.author, Some("Test Author <test@example.com>".to_string()));
         assert_eq!(cli.directory, Some(PathBuf::from("/tmp/test")));
         assert_eq!(cli.config, Some(PathBuf::from("config.yml")));
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+        assert!(cli.no_command_expansion);
         assert!(cli.no_git);
         assert!(cli.no_sample_config);
         assert!(cli.no_verify);
@@ -106,19 +259,91 @@ mod tests {
             "/tmp/short",
             "-c",
             "short.yml",
+            "-p",
+            "personal",
         ])
         .unwrap();
 
-        assert_eq!(cli.project, "test-project");
+        assert_eq!(cli.project.as_deref(), Some("test-project"));
         assert_eq!(cli.author, Some("Short Author".to_string()));
         assert_eq!(cli.directory, Some(PathBuf::from("/tmp/short")));
         assert_eq!(cli.config, Some(PathBuf::from("short.yml")));
+        assert_eq!(cli.profile.as_deref(), Some("personal"));
+    }
+
+    #[test]
+    fn test_cli_project_name_is_optional() {
+        let cli = Cli::try_parse_from(["scaffold"]).unwrap();
+        assert!(cli.project.is_none());
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["scaffold", ".", "-y"]).unwrap();
+        assert_eq!(cli.project.as_deref(), Some("."));
+        assert!(cli.yes);
+    }
+
+    #[test]
+    fn test_cli_parses_regen_cli_subcommand() {
+        let cli = Cli::try_parse_from(["scaffold", "regen-cli", "-d", "tool"]).unwrap();
+
+        match cli.command {
+            Some(Command::RegenCli { spec, directory }) => {
+                assert!(spec.is_none());
+                assert_eq!(directory, PathBuf::from("tool"));
+            }
+            _ => panic!("expected regen-cli"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_config_schema_subcommand() {
+        let cli = Cli::try_parse_from(["scaffold", "config", "schema"]).unwrap();
+
+        assert!(cli.project.is_none());
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                action: ConfigAction::Schema
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parses_config_show_with_global_args() {
+        let cli = Cli::try_parse_from(["scaffold", "config", "show", "--profile", "work", "-c", "team.yml"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                action: ConfigAction::Show
+            })
+        ));
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+        assert_eq!(cli.config, Some(PathBuf::from("team.yml")));
     }
 
     #[test]
-    fn test_cli_requires_project_name() {
-        let result = Cli::try_parse_from(["scaffold"]);
-        assert!(result.is_err());
+    fn test_cli_parses_repeated_components() {
+        let cli = Cli::try_parse_from(["scaffold", "tool", "--component", "bench"]).unwrap();
+        assert_eq!(cli.components, vec![Component::Bench]);
+
+        let cli = Cli::try_parse_from(["scaffold", "tool"]).unwrap();
+        assert!(cli.components.is_empty());
+        assert!(Cli::try_parse_from(["scaffold", "tool", "--component", "nope"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_completions_and_man_subcommands() {
+        let cli = Cli::try_parse_from(["scaffold", "completions", "zsh"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Completions { shell: Shell::Zsh })));
+
+        let cli = Cli::try_parse_from(["scaffold", "man", "--dir", "man"]).unwrap();
+        match cli.command {
+            Some(Command::Man { dir }) => assert_eq!(dir, Some(PathBuf::from("man"))),
+            _ => panic!("expected man"),
+        }
+
+        assert!(Cli::try_parse_from(["scaffold", "completions", "tcsh"]).is_err());
     }
 
     #[test]
@@ -134,7 +359,7 @@ mod tests {
 
         for name in valid_names.iter() {
             let cli = Cli::try_parse_from(["scaffold", name]).unwrap();
-            assert_eq!(cli.project, *name);
+            assert_eq!(cli.project.as_deref(), Some(*name));
         }
     }
 
@@ -172,6 +397,7 @@ mod tests {
     fn test_cli_boolean_flags_default_false() {
         let cli = Cli::try_parse_from(["scaffold", "test"]).unwrap();
 
+        assert!(!cli.no_command_expansion);
         assert!(!cli.no_git);
         assert!(!cli.no_sample_config);
         assert!(!cli.no_verify);
@@ -185,5 +411,6 @@ mod tests {
         assert!(cli.author.is_none());
         assert!(cli.directory.is_none());
         assert!(cli.config.is_none());
+        assert!(cli.profile.is_none());
     }
 }
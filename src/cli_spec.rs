@@ -0,0 +1,318 @@
+use crate::crate_name;
+use eyre::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Where a generated project keeps its spec, so `scaffold regen-cli` finds it
+pub const SPEC_FILE: &str = "cli-spec.yml";
+
+/// Arguments every generated CLI already has; spec arguments can't reuse their names
+const BUILTIN_ARGS: &[&str] = &["config", "set", "verbose", "quiet", "help", "version"];
+
+/// Subcommands generated CLIs already have, counting the optional hidden ones; spec commands can't reuse their names
+pub const BUILTIN_COMMANDS: &[&str] = &["config", "completions", "man"];
+
+/// Declarative description of a generated tool's interface, compiled into clap derives
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CliSpec {
+    /// One-line description shown by --help
+    pub about: Option<String>,
+    /// Flags and options accepted before the subcommand
+    #[serde(default)]
+    pub args: Vec<ArgSpec>,
+    /// Subcommands, each with a handler module under src/commands/
+    pub commands: Vec<CommandSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandSpec {
+    pub name: String,
+    pub about: Option<String>,
+    #[serde(default)]
+    pub args: Vec<ArgSpec>,
+    /// Invocations the generated tests assert the parser accepts, without the binary name
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArgSpec {
+    pub name: String,
+    #[serde(default)]
+    pub kind: ArgKind,
+    #[serde(default, rename = "type")]
+    pub value_type: ValueType,
+    pub short: Option<char>,
+    pub help: Option<String>,
+    pub default: Option<String>,
+    /// Environment variable read when the argument isn't given
+    pub env: Option<String>,
+    /// Defaults to true for positionals and false for options
+    pub required: Option<bool>,
+    /// Accept the argument more than once, collecting a Vec
+    #[serde(default)]
+    pub multiple: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgKind {
+    /// `--name <value>`
+    #[default]
+    Option,
+    /// `--name`, a bool
+    Flag,
+    /// A bare value
+    Positional,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueType {
+    #[default]
+    String,
+    Path,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+    I32,
+    I64,
+    F64,
+}
+
+impl ValueType {
+    /// Rust type of a single value
+    pub fn rust_type(self) -> &'static str {
+        match self {
+            Self::String => "String",
+            Self::Path => "PathBuf",
+            Self::Bool => "bool",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::Usize => "usize",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::F64 => "f64",
+        }
+    }
+
+    /// A value that parses as this type, used in generated tests
+    pub fn sample(self) -> &'static str {
+        match self {
+            Self::String => "example",
+            Self::Path => "example.txt",
+            Self::Bool => "true",
+            Self::F64 => "1.5",
+            _ => "1",
+        }
+    }
+}
+
+impl ArgSpec {
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(self.kind == ArgKind::Positional)
+    }
+}
+
+impl CliSpec {
+    /// Read and validate a spec file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).context(format!("Failed to read CLI spec {}", path.display()))?;
+        let spec: Self =
+            serde_yaml::from_str(&content).context(format!("Failed to parse CLI spec {}", path.display()))?;
+        spec.validate()
+            .context(format!("Invalid CLI spec {}", path.display()))?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.commands.is_empty() {
+            return Err(eyre::eyre!("A CLI spec must define at least one command"));
+        }
+
+        validate_args(&self.args, true)?;
+        for command in &self.commands {
+            identifier("command", &command.name)?;
+            if BUILTIN_COMMANDS.contains(&command.name.as_str()) {
+                return Err(eyre::eyre!(
+                    "Command '{}' is reserved by the generated CLI",
+                    command.name
+                ));
+            }
+            validate_args(&command.args, false).context(format!("In command '{}'", command.name))?;
+            if self.commands.iter().filter(|other| other.name == command.name).count() > 1 {
+                return Err(eyre::eyre!("Command '{}' is listed more than once", command.name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether any argument reads an environment variable, which needs clap's `env` feature
+    pub fn uses_env(&self) -> bool {
+        self.args
+            .iter()
+            .chain(self.commands.iter().flat_map(|command| &command.args))
+            .any(|arg| arg.env.is_some())
+    }
+}
+
+fn validate_args(args: &[ArgSpec], top_level: bool) -> Result<()> {
+    for (index, arg) in args.iter().enumerate() {
+        let field = identifier("argument", &arg.name)?;
+        if BUILTIN_ARGS.contains(&field.as_str()) {
+            return Err(eyre::eyre!("Argument '{}' is reserved by the generated CLI", arg.name));
+        }
+        if args[..index].iter().any(|other| other.name == arg.name) {
+            return Err(eyre::eyre!("Argument '{}' is listed more than once", arg.name));
+        }
+        if top_level && arg.kind == ArgKind::Positional {
+            return Err(eyre::eyre!(
+                "Top-level argument '{}' can't be positional, it would clash with the command name",
+                arg.name
+            ));
+        }
+
+        if let Some(short) = arg.short {
+            let taken = ['h', 'V'].contains(&short) || (top_level && ['c', 'v', 'q'].contains(&short));
+            if !short.is_ascii_alphanumeric() || taken {
+                return Err(eyre::eyre!("Argument '{}' can't use -{}", arg.name, short));
+            }
+            if arg.kind == ArgKind::Positional {
+                return Err(eyre::eyre!(
+                    "Positional argument '{}' can't have a short flag",
+                    arg.name
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a command or argument name and return it as a snake_case Rust identifier
+pub fn identifier(what: &str, name: &str) -> Result<String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.ends_with('-')
+        && !name.contains("--");
+    if !valid {
+        return Err(eyre::eyre!(
+            "Invalid {} name '{}' (use lowercase letters, digits and single hyphens, starting with a letter)",
+            what,
+            name
+        ));
+    }
+
+    let snake = name.replace('-', "_");
+    if crate_name::is_keyword(&snake) || name == "help" {
+        return Err(eyre::eyre!("{} name '{}' is reserved", capitalize(what), name));
+    }
+
+    Ok(snake)
+}
+
+/// `dry-run` as a type name: `DryRun`
+pub fn pascal_case(name: &str) -> String {
+    name.split('-').map(capitalize).collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn load_str(content: &str) -> Result<CliSpec> {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cli-spec.yml");
+        fs::write(&path, content).unwrap();
+        CliSpec::load(&path)
+    }
+
+    #[test]
+    fn test_load_parses_commands_and_args() {
+        let spec = load_str(
+            r#"
+about: Manage widgets
+args:
+  - name: color
+    kind: flag
+    short: C
+commands:
+  - name: add
+    about: Add a widget
+    args:
+      - name: widget
+        kind: positional
+      - name: count
+        type: u32
+        default: "1"
+        env: WIDGET_COUNT
+    examples:
+      - add gear --count 3
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(spec.about.as_deref(), Some("Manage widgets"));
+        assert_eq!(spec.args[0].kind, ArgKind::Flag);
+        let add = &spec.commands[0];
+        assert!(add.args[0].is_required());
+        assert!(!add.args[1].is_required());
+        assert_eq!(add.args[1].value_type, ValueType::U32);
+        assert_eq!(add.examples, ["add gear --count 3"]);
+        assert!(spec.uses_env());
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_specs() {
+        let cases = [
+            ("commands: []", "at least one command"),
+            ("commands:\n  - name: Add", "Invalid command name"),
+            ("commands:\n  - name: a\n  - name: a", "more than once"),
+            ("commands:\n  - name: a\n    args:\n      - name: config", "reserved"),
+            (
+                "args:\n  - name: file\n    kind: positional\ncommands:\n  - name: a",
+                "can't be positional",
+            ),
+            (
+                "args:\n  - name: silent\n    short: q\ncommands:\n  - name: a",
+                "can't use -q",
+            ),
+            ("commands:\n  - name: a\n    flags: []", "unknown field"),
+            ("commands:\n  - name: config", "reserved"),
+        ];
+
+        for (content, expected) in cases {
+            let error = format!("{:?}", load_str(content).unwrap_err());
+            assert!(error.contains(expected), "{} -> {}", content, error);
+        }
+    }
+
+    #[test]
+    fn test_identifier_and_pascal_case() {
+        assert_eq!(identifier("command", "sync-all").unwrap(), "sync_all");
+        assert!(identifier("command", "type").is_err());
+        assert_eq!(pascal_case("v2-sync"), "V2Sync");
+    }
+}
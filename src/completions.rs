@@ -0,0 +1,247 @@
+use crate::cli::Cli;
+use crate::config::{Component, Config, ProjectKind};
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::env::Shells;
+use clap_complete::{CompleteEnv, CompletionCandidate, Shell};
+use eyre::{Context, Result};
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Environment variable the registered completion scripts set when calling back into scaffold
+const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Answer a completion request from a registered script and exit; returns on a regular run
+pub fn handle_completion_request() {
+    CompleteEnv::with_factory(Cli::command).var(COMPLETE_VAR).complete();
+}
+
+/// Print the script that registers scaffold's completions with `shell`
+///
+/// The script calls back into scaffold on every completion, so profile, kind and component candidates follow the config files.
+pub fn print_registration(shell: Shell) -> Result<()> {
+    let completer = std::env::current_exe().map_or_else(|_| "scaffold".to_string(), |exe| exe.display().to_string());
+    write_registration(shell, &completer, &mut std::io::stdout())
+}
+
+fn write_registration(shell: Shell, completer: &str, buf: &mut dyn Write) -> Result<()> {
+    let name = shell.to_string();
+    let shells = Shells::builtins();
+    let env_completer = shells
+        .completer(&name)
+        .ok_or_else(|| eyre::eyre!("No completion support for {}", name))?;
+
+    env_completer
+        .write_registration(COMPLETE_VAR, "scaffold", "scaffold", completer, buf)
+        .context(format!("Failed to write {} completions", name))
+}
+
+/// Print scaffold's man page, or write one for scaffold and each subcommand to `dir`
+pub fn man(dir: Option<&Path>) -> Result<()> {
+    match dir {
+        Some(dir) => {
+            fs::create_dir_all(dir).context(format!("Failed to create {}", dir.display()))?;
+            clap_mangen::generate_to(Cli::command(), dir).context("Failed to write man pages")?;
+            println!("Wrote man pages to {}", dir.display());
+            Ok(())
+        }
+        None => clap_mangen::Man::new(Cli::command())
+            .render(&mut std::io::stdout())
+            .context("Failed to render man page"),
+    }
+}
+
+/// Profiles of the config a `scaffold` run with the arguments being completed would load
+pub fn profile_candidates() -> Vec<CompletionCandidate> {
+    let config_path = config_arg(std::env::args_os());
+    profile_names(config_path.as_ref())
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Project kinds (the templates `--kind` picks), the one the loaded config defaults to marked as such
+pub fn kind_candidates() -> Vec<CompletionCandidate> {
+    kinds(config_arg(std::env::args_os()).as_ref())
+}
+
+/// Components, the ones the loaded config already enables marked as such
+pub fn component_candidates() -> Vec<CompletionCandidate> {
+    components(config_arg(std::env::args_os()).as_ref())
+}
+
+/// Profile names from `config_path`, or from the config files found from the current directory
+fn profile_names(config_path: Option<&PathBuf>) -> Vec<String> {
+    load_config(config_path)
+        .map(|config| config.profile_names().into_iter().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+fn kinds(config_path: Option<&PathBuf>) -> Vec<CompletionCandidate> {
+    let config = load_config(config_path).unwrap_or_default();
+    value_candidates(|kind: &ProjectKind| *kind == config.template.kind, "template.kind")
+}
+
+fn components(config_path: Option<&PathBuf>) -> Vec<CompletionCandidate> {
+    let config = load_config(config_path).unwrap_or_default();
+    value_candidates(
+        |component: &Component| config.template.has_component(*component),
+        "in template.components",
+    )
+}
+
+/// The config a `scaffold` run would load with `config_path`, or None when it is missing or broken
+fn load_config(config_path: Option<&PathBuf>) -> Option<Config> {
+    // A broken config has nothing to offer; the completion must not print errors
+    Config::load(config_path, Some(Path::new(".")), false).ok()
+}
+
+/// Every value of `T` with its doc help, noting `configured` on the ones the config selects
+fn value_candidates<T: ValueEnum>(selected: impl Fn(&T) -> bool, configured: &str) -> Vec<CompletionCandidate> {
+    T::value_variants()
+        .iter()
+        .filter_map(|variant| {
+            let value = variant.to_possible_value()?;
+            let help = value.get_help().map(ToString::to_string).unwrap_or_default();
+            let help = if selected(variant) {
+                format!("{} ({})", help, configured)
+            } else {
+                help
+            };
+            Some(CompletionCandidate::new(value.get_name()).help(Some(help.into())))
+        })
+        .collect()
+}
+
+/// The value of the last `-c`/`--config` among `args`
+fn config_arg(args: impl IntoIterator<Item = OsString>) -> Option<PathBuf> {
+    let mut config = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let arg = arg.to_string_lossy().into_owned();
+        if arg == "-c" || arg == "--config" {
+            config = args.next().map(PathBuf::from);
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            config = Some(PathBuf::from(path));
+        }
+    }
+    config
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn os_args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_config_arg() {
+        assert_eq!(
+            config_arg(os_args(&["scaffold", "--", "scaffold", "--profile", ""])),
+            None
+        );
+        assert_eq!(
+            config_arg(os_args(&[
+                "scaffold",
+                "--",
+                "scaffold",
+                "-c",
+                "team.yml",
+                "--profile",
+                ""
+            ])),
+            Some(PathBuf::from("team.yml"))
+        );
+        assert_eq!(
+            config_arg(os_args(&["scaffold", "--config=a.yml", "--config", "b.yml"])),
+            Some(PathBuf::from("b.yml"))
+        );
+    }
+
+    #[test]
+    fn test_profile_names_from_explicit_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("scaffold.yml");
+        fs::write(
+            &config_path,
+            "profiles:\n  work:\n    default-author: Work <w@example.com>\n  oss:\n    default-author: Me <m@example.com>\n",
+        )
+        .unwrap();
+
+        assert_eq!(profile_names(Some(&config_path)), vec!["oss", "work"]);
+        let config = load_config(Some(&config_path)).unwrap();
+        assert_eq!(
+            config.with_profile(Some("work")).unwrap().default_author,
+            "Work <w@example.com>"
+        );
+        assert!(profile_names(Some(&temp_dir.path().join("missing.yml"))).is_empty());
+    }
+
+    fn values_and_help(candidates: Vec<CompletionCandidate>) -> Vec<(String, String)> {
+        candidates
+            .into_iter()
+            .map(|candidate| {
+                let help = candidate.get_help().map(ToString::to_string).unwrap_or_default();
+                (candidate.get_value().to_string_lossy().into_owned(), help)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kinds_and_components_from_explicit_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("scaffold.yml");
+        fs::write(&config_path, "template:\n  kind: workspace\n  components: [bench]\n").unwrap();
+
+        let kinds = values_and_help(kinds(Some(&config_path)));
+        assert_eq!(
+            kinds.iter().map(|(value, _)| value.as_str()).collect::<Vec<_>>(),
+            vec!["package", "workspace"]
+        );
+        assert!(!kinds[0].1.contains("(template.kind)"));
+        assert!(kinds[1].1.ends_with("(template.kind)"));
+
+        let components = values_and_help(components(Some(&config_path)));
+        assert_eq!(
+            components.iter().map(|(value, _)| value.as_str()).collect::<Vec<_>>(),
+            vec!["bench", "testing"]
+        );
+        assert!(components[0].1.starts_with("Criterion benchmark"));
+        assert!(components[0].1.ends_with("(in template.components)"));
+        assert!(!components[1].1.contains("template.components"));
+    }
+
+    #[test]
+    fn test_kinds_and_components_without_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("missing.yml");
+
+        assert_eq!(kinds(Some(&missing)).len(), 2);
+        assert_eq!(components(Some(&missing)).len(), 2);
+    }
+
+    #[test]
+    fn test_write_registration_calls_back_into_scaffold() {
+        let mut buf = Vec::new();
+        write_registration(Shell::Bash, "/usr/local/bin/scaffold", &mut buf).unwrap();
+
+        let script = String::from_utf8(buf).unwrap();
+        assert!(script.contains("COMPLETE="));
+        assert!(script.contains("/usr/local/bin/scaffold"));
+    }
+
+    #[test]
+    fn test_man_page_renders() {
+        let mut buf = Vec::new();
+        clap_mangen::Man::new(Cli::command()).render(&mut buf).unwrap();
+
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.contains("scaffold"));
+        assert!(page.contains("completions"));
+    }
+}
@@ -1,54 +1,306 @@
+use crate::conflict::{ConflictPolicy, ConflictRule};
+use crate::expand;
+use clap::ValueEnum;
 use eyre::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize, Serialize)]
+const CONFIG_FILE_NAME: &str = "scaffold.yml";
+
+/// Author used when neither config nor auto-detection provides one
+pub const PLACEHOLDER_AUTHOR: &str = "Your Name <your.email@example.com>";
+
+/// Directories that mark the root of a repository, where config discovery stops
+const VCS_MARKERS: &[&str] = &[".git", ".hg", ".jj", ".svn"];
+
+/// Configuration for the scaffold tool (scaffold.yml)
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct Config {
+    /// Author written to generated Cargo.toml files when --author is omitted; auto-detected when unset
     pub default_author: String,
+    /// License identifier for generated projects
     pub default_license: String,
+    /// Whether to initialize a git repository in generated projects
     pub create_git_repo: bool,
+    /// Whether to create a sample config file in generated projects
     pub create_sample_config: bool,
+    /// Enable debug logging
     pub debug: bool,
+    /// Allow $(command) expansion in string values; only honoured in the user config or a --config file,
+    /// and --no-command-expansion always disables it
+    pub expand_commands: bool,
+    /// Controls what gets generated
     pub template: TemplateConfig,
+    /// Extra glob patterns for top-level entries allowed in an existing directory, e.g. ".envrc" or "*.nix"
+    pub allowed_files: Vec<String>,
+    /// What to do with files that already exist; --on-conflict takes precedence
+    pub on_conflict: Option<ConflictPolicy>,
+    /// Per-path conflict policies, first match wins over --on-conflict and on-conflict
+    pub conflict_rules: Vec<ConflictRule>,
+    /// Profile applied when --profile is not given
+    pub default_profile: Option<String>,
+    /// Named overlays applied on top of the base config with --profile
+    #[schemars(with = "HashMap<String, Config>")]
+    pub profiles: HashMap<String, serde_yaml::Value>,
+    /// Name of the profile that was applied, if any
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+    /// Config files that were loaded, least specific first
+    #[serde(skip)]
+    pub sources: Vec<PathBuf>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Template configuration for generated projects
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct TemplateConfig {
+    /// Project layout; --kind takes precedence
+    pub kind: ProjectKind,
+    /// Generate build.rs with git describe version wiring
     pub create_build_rs: bool,
+    /// Generate src/cli.rs with clap definitions
     pub create_cli_module: bool,
+    /// Generate src/config.rs with config loading
     pub create_config_module: bool,
+    /// Generate src/lib.rs exposing config and run(cli, config), leaving main.rs a thin wrapper
+    pub create_lib: bool,
+    /// Add hidden `completions <shell>` and `man <dir>` subcommands, packaged by the release workflow and install task
+    pub create_completions: bool,
+    /// Logging backend wired into the generated setup_logging(); --logging takes precedence
+    pub logging: LoggingBackend,
+    /// File format of the generated project's config; --config-format takes precedence
+    pub config_format: ConfigFormat,
+    /// Optional components to generate; --component adds to them
+    pub components: Vec<Component>,
+    /// Dependencies added to the generated Cargo.toml
     pub dependencies: Vec<Dependency>,
+    /// Dev-dependencies added to the generated Cargo.toml, used by the generated tests
+    pub dev_dependencies: Vec<Dependency>,
+    /// Values written to the generated sample config file
+    #[schemars(with = "HashMap<String, serde_json::Value>")]
     pub sample_config: HashMap<String, serde_yaml::Value>,
+    /// Settings for the generated CLI
     pub cli: CliConfig,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Layout of a generated project
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectKind {
+    /// A single package with the binary at the root
+    #[default]
+    Package,
+    /// A virtual workspace with crates/<name>-cli and crates/<name>-core
+    Workspace,
+}
+
+/// Crates that only one logging backend or config format uses, dropped from the dependencies when another is selected
+const SWAPPED_CRATES: &[&str] = &[
+    "env_logger",
+    "fern",
+    "tracing-subscriber",
+    "serde_yaml",
+    "toml",
+    "serde_json",
+];
+
+/// How generated projects log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoggingBackend {
+    /// env_logger writing to the log file
+    #[default]
+    EnvLogger,
+    /// fern writing to both stderr and the log file
+    Fern,
+    /// tracing-subscriber writing to the log file
+    Tracing,
+}
+
+impl LoggingBackend {
+    /// Crates the generated setup_logging() needs besides `log`
+    fn dependencies(self) -> Vec<Dependency> {
+        match self {
+            Self::EnvLogger => vec![dep("env_logger", &[])],
+            Self::Fern => vec![dep("fern", &[]), dep("humantime", &[])],
+            Self::Tracing => vec![dep("tracing-subscriber", &["env-filter"])],
+        }
+    }
+}
+
+/// File format generated projects read their config from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    /// <project>.yml, parsed with serde_yaml
+    #[default]
+    Yaml,
+    /// <project>.toml, parsed with toml
+    Toml,
+    /// <project>.json, parsed with serde_json
+    Json,
+}
+
+impl ConfigFormat {
+    /// Extension of the sample config and of the files `Config::load` looks for
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Yaml => "yml",
+            Self::Toml => "toml",
+            Self::Json => "json",
+        }
+    }
+
+    /// Crate the generated `Config::load_from_file` parses with
+    fn dependencies(self) -> Vec<Dependency> {
+        match self {
+            Self::Yaml => vec![dep("serde_yaml", &[])],
+            Self::Toml => vec![dep("toml", &[])],
+            Self::Json => vec![dep("serde_json", &[])],
+        }
+    }
+}
+
+/// Optional pieces a generated project can include
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Component {
+    /// Criterion benchmark in benches/<project>.rs, an otto bench task and a CI benchmark job
+    Bench,
+    /// Proptest round-trip of the generated Config, a cargo-fuzz target for its parser and otto tasks for both
+    Testing,
+}
+
+impl Component {
+    /// Dev-dependencies the component's generated code needs
+    fn dev_dependencies(self) -> Vec<Dependency> {
+        match self {
+            Self::Bench => vec![dep("criterion", &[])],
+            Self::Testing => vec![dep("proptest", &[])],
+        }
+    }
+
+    /// What the component uses the project's lib target for; every component works on its Config
+    fn lib_use(self) -> &'static str {
+        match self {
+            Self::Bench => "bench component needs a lib target so its benchmark can parse into Config",
+            Self::Testing => "testing component needs a lib target so its fuzz target can parse into Config",
+        }
+    }
+}
+
+fn dep(name: &str, features: &[&str]) -> Dependency {
+    Dependency {
+        name: name.to_string(),
+        features: features.iter().map(|feature| feature.to_string()).collect(),
+    }
+}
+
+/// A crate dependency added to generated projects
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct Dependency {
+    /// Crate name as published on crates.io
     pub name: String,
+    /// Cargo features to enable
     #[serde(default)]
     pub features: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Settings for the generated CLI
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct CliConfig {
+    /// Text shown after --help; {{PROJECT}} is replaced with the project name
     pub after_help: String,
+    /// Subcommands to generate, each with a handler module under src/commands/; empty for a flat CLI
+    pub subcommands: Vec<String>,
+    /// CLI spec (YAML) to compile into cli.rs, handler stubs and parser tests; --cli-spec takes precedence
+    pub spec: Option<PathBuf>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            default_author: "Your Name <your.email@example.com>".to_string(),
+            default_author: PLACEHOLDER_AUTHOR.to_string(),
             default_license: "MIT".to_string(),
             create_git_repo: true,
             create_sample_config: true,
             debug: false,
+            expand_commands: false,
             template: TemplateConfig::default(),
+            allowed_files: Vec::new(),
+            on_conflict: None,
+            conflict_rules: Vec::new(),
+            default_profile: None,
+            profiles: HashMap::new(),
+            active_profile: None,
+            sources: Vec::new(),
+        }
+    }
+}
+
+impl TemplateConfig {
+    /// Dependencies to add, with the crates of the logging backend and config format swapped in for the others'
+    pub fn resolved_dependencies(&self) -> Vec<Dependency> {
+        let mut selected = self.logging.dependencies();
+        selected.extend(self.config_format.dependencies());
+        if self.create_completions {
+            selected.extend([dep("clap_complete", &[]), dep("clap_mangen", &[])]);
+        }
+        let replaced = |name: &str| SWAPPED_CRATES.contains(&name) && !selected.iter().any(|dep| dep.name == name);
+
+        let mut dependencies: Vec<Dependency> = self
+            .dependencies
+            .iter()
+            .filter(|dep| !replaced(&dep.name))
+            .cloned()
+            .collect();
+        for dep in selected {
+            if !dependencies.iter().any(|existing| existing.name == dep.name) {
+                dependencies.push(dep);
+            }
+        }
+
+        dependencies
+    }
+
+    /// Dev-dependencies to add, plus those of the selected components
+    pub fn resolved_dev_dependencies(&self) -> Vec<Dependency> {
+        let mut dev_dependencies = self.dev_dependencies.clone();
+        for dep in self
+            .components
+            .iter()
+            .flat_map(|component| component.dev_dependencies())
+        {
+            if !dev_dependencies.iter().any(|existing| existing.name == dep.name) {
+                dev_dependencies.push(dep);
+            }
         }
+
+        dev_dependencies
+    }
+
+    /// Whether `component` is selected
+    pub fn has_component(&self, component: Component) -> bool {
+        self.components.contains(&component)
+    }
+
+    /// Reject component selections the rest of the template can't support
+    pub fn check_components(&self) -> Result<()> {
+        // Components work on the project's Config, which only a lib target exports
+        if !self.create_lib
+            && let Some(component) = self.components.first()
+        {
+            return Err(eyre::eyre!(
+                "The {} (pass --lib or set template.create-lib: true)",
+                component.lib_use()
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -63,9 +315,15 @@ impl Default for TemplateConfig {
         sample_config.insert("debug".to_string(), serde_yaml::Value::Bool(false));
 
         Self {
+            kind: ProjectKind::default(),
             create_build_rs: true,
             create_cli_module: true,
             create_config_module: true,
+            create_lib: false,
+            create_completions: false,
+            logging: LoggingBackend::default(),
+            config_format: ConfigFormat::default(),
+            components: Vec::new(),
             dependencies: vec![
                 Dependency {
                     name: "clap".to_string(),
@@ -100,6 +358,20 @@ impl Default for TemplateConfig {
                     features: vec![],
                 },
             ],
+            dev_dependencies: vec![
+                Dependency {
+                    name: "tempfile".to_string(),
+                    features: vec![],
+                },
+                Dependency {
+                    name: "assert_cmd".to_string(),
+                    features: vec![],
+                },
+                Dependency {
+                    name: "predicates".to_string(),
+                    features: vec![],
+                },
+            ],
             sample_config,
             cli: CliConfig::default(),
         }
@@ -110,57 +382,235 @@ impl Default for CliConfig {
     fn default() -> Self {
         Self {
             after_help: "Logs are written to: ~/.local/share/{{PROJECT}}/logs/{{PROJECT}}.log".to_string(),
+            subcommands: Vec::new(),
+            spec: None,
         }
     }
 }
 
 impl Config {
     /// Load configuration with fallback chain
-    pub fn load(config_path: Option<&PathBuf>) -> Result<Self> {
+    ///
+    /// Without an explicit path, config files are layered from least to most specific:
+    /// defaults, `<config dir>/scaffold/scaffold.yml`, then every `scaffold.yml` found walking
+    /// up from the cwd and the target directory (each walk stops at the nearest VCS root).
+    ///
+    /// `${VAR}` references in string values are expanded; `$(command)` references only when
+    /// `allow_commands` is true and the user config (or the explicit file) sets `expand-commands: true`.
+    /// Discovered project layers never run commands, so scaffolding inside a cloned repo is safe.
+    pub fn load(config_path: Option<&PathBuf>, target_dir: Option<&Path>, allow_commands: bool) -> Result<Self> {
         // If explicit config path provided, try to load it
         if let Some(path) = config_path {
-            return Self::load_from_file(path).context(format!("Failed to load config from {}", path.display()));
+            return Self::load_from_file(path, allow_commands)
+                .context(format!("Failed to load config from {}", path.display()));
         }
 
-        // Try primary location: ~/.config/scaffold/scaffold.yml
-        if let Some(config_dir) = dirs::config_dir() {
-            let primary_config = config_dir.join("scaffold").join("scaffold.yml");
-            if primary_config.exists() {
-                match Self::load_from_file(&primary_config) {
-                    Ok(config) => return Ok(config),
-                    Err(e) => {
-                        log::warn!("Failed to load config from {}: {}", primary_config.display(), e);
-                    }
-                }
+        let mut layers = Vec::new();
+
+        // Base layer: <platform config dir>/scaffold/scaffold.yml
+        let user_config = dirs::config_dir()
+            .map(|config_dir| config_dir.join("scaffold").join(CONFIG_FILE_NAME))
+            .filter(|path| path.is_file());
+        if let Some(user_config) = &user_config {
+            layers.push(user_config.clone());
+        }
+
+        // Project layers: scaffold.yml files in the cwd, the target directory and their parents
+        let mut start_dirs = Vec::new();
+        match std::env::current_dir() {
+            Ok(cwd) => start_dirs.push(cwd),
+            Err(e) => log::warn!("Failed to determine current directory: {}", e),
+        }
+        if let Some(dir) = target_dir {
+            match std::path::absolute(dir) {
+                Ok(dir) => start_dirs.push(dir),
+                Err(e) => log::warn!("Failed to resolve {}: {}", dir.display(), e),
             }
         }
+        for path in discover_config_files(&start_dirs) {
+            if !layers.contains(&path) {
+                layers.push(path);
+            }
+        }
+
+        Self::load_layers(&layers, user_config.as_deref(), allow_commands)
+    }
+
+    /// Merge config files in order, later files overriding earlier ones; unreadable files are skipped
+    ///
+    /// Each layer is expanded on its own; only `user_config` may enable `$(command)` expansion.
+    fn load_layers(paths: &[PathBuf], user_config: Option<&Path>, allow_commands: bool) -> Result<Self> {
+        let mut merged = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        let mut loaded = Vec::new();
+        let mut expand_commands = false;
 
-        // Try fallback location: ./scaffold.yml
-        let fallback_config = PathBuf::from("scaffold.yml");
-        if fallback_config.exists() {
-            match Self::load_from_file(&fallback_config) {
-                Ok(config) => return Ok(config),
-                Err(e) => {
-                    log::warn!("Failed to load config from {}: {}", fallback_config.display(), e);
+        for path in paths {
+            match Self::read_layer(path) {
+                Ok(Some(mut value)) => {
+                    let trusted = user_config == Some(path.as_path());
+                    if !trusted && sets_expand_commands(&value) {
+                        log::warn!(
+                            "Ignoring expand-commands in {}: only the user config or --config can enable it",
+                            path.display()
+                        );
+                    }
+                    expand_commands |= Self::expand_layer(&mut value, allow_commands && trusted)
+                        .context(format!("Failed to expand {}", path.display()))?;
+                    merge_yaml(&mut merged, value);
+                    loaded.push(path.clone());
+                    log::info!("Loaded config from: {}", path.display());
                 }
+                Ok(None) => log::info!("Skipping empty config file: {}", path.display()),
+                Err(e) => log::warn!("Failed to load config from {}: {}", path.display(), e),
             }
         }
 
-        // No config file found, use defaults
-        log::info!("No config file found, using defaults");
-        Ok(Self::default())
+        if loaded.is_empty() {
+            // No config file found, use defaults
+            log::info!("No config file found, using defaults");
+            return Ok(Self::default());
+        }
+
+        let mut config: Self = serde_yaml::from_value(merged).context("Failed to merge config files")?;
+        config.expand_commands = expand_commands;
+        config.sources = loaded;
+        Ok(config)
+    }
+
+    /// Expand variable and command references, then deserialize
+    fn from_value(mut value: serde_yaml::Value, allow_commands: bool) -> Result<Self> {
+        Self::expand_layer(&mut value, allow_commands)?;
+
+        serde_yaml::from_value(value).context("Failed to parse config file")
     }
 
-    fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let content = fs::read_to_string(&path).context("Failed to read config file")?;
+    /// Expand the references of one config file; returns whether `$(command)` expansion was on
+    fn expand_layer(value: &mut serde_yaml::Value, allow_commands: bool) -> Result<bool> {
+        let expand_commands = allow_commands && sets_expand_commands(value);
+        expand::expand_yaml(value, expand_commands)?;
+        Ok(expand_commands)
+    }
 
-        let config: Self = serde_yaml::from_str(&content).context("Failed to parse config file")?;
+    /// Read a config file as raw YAML, validating it against the config types
+    fn read_layer(path: &Path) -> Result<Option<serde_yaml::Value>> {
+        let content = fs::read_to_string(path).context("Failed to read config file")?;
+        let value: serde_yaml::Value = serde_yaml::from_str(&content).context("Failed to parse config file")?;
+
+        if value.is_null() {
+            return Ok(None);
+        }
+
+        serde_yaml::from_value::<Self>(value.clone()).context("Failed to parse config file")?;
+        Ok(Some(value))
+    }
+
+    /// Overlay a named profile, falling back to `default-profile` when no name is given
+    pub fn with_profile(self, profile: Option<&str>) -> Result<Self> {
+        let Some(name) = profile.map(str::to_string).or_else(|| self.default_profile.clone()) else {
+            return Ok(self);
+        };
+
+        let Some(overlay) = self.profiles.get(&name) else {
+            let available = self.profile_names();
+            return Err(eyre::eyre!(
+                "Unknown profile '{}' (available: {})",
+                name,
+                if available.is_empty() {
+                    "none defined".to_string()
+                } else {
+                    available.join(", ")
+                }
+            ));
+        };
+
+        let mut merged = serde_yaml::to_value(&self).context("Failed to serialize base config")?;
+        merge_yaml(&mut merged, overlay.clone());
+
+        let mut config: Self = serde_yaml::from_value(merged).context(format!("Failed to apply profile '{}'", name))?;
+        log::info!("Applied profile: {}", name);
+        config.active_profile = Some(name);
+        config.sources = self.sources;
+        Ok(config)
+    }
+
+    /// Names of the profiles defined in this config, sorted
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// JSON Schema for scaffold.yml, derived from the config types
+    pub fn json_schema() -> Result<String> {
+        let schema = schemars::schema_for!(Config);
+        serde_json::to_string_pretty(&schema).context("Failed to serialize config schema")
+    }
+
+    fn load_from_file<P: AsRef<Path>>(path: P, allow_commands: bool) -> Result<Self> {
+        let value = Self::read_layer(path.as_ref())?.unwrap_or(serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+
+        let mut config = Self::from_value(value, allow_commands)?;
+        config.sources = vec![path.as_ref().to_path_buf()];
 
         log::info!("Loaded config from: {}", path.as_ref().display());
         Ok(config)
     }
 }
 
+/// Whether a raw config file sets `expand-commands: true`
+fn sets_expand_commands(value: &serde_yaml::Value) -> bool {
+    value
+        .get("expand-commands")
+        .and_then(serde_yaml::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// `scaffold.yml` files found walking up from each start directory, least specific first
+///
+/// Each walk stops at the filesystem root or the first directory containing a VCS marker.
+/// Files seen from an earlier start directory are not repeated.
+fn discover_config_files(start_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = Vec::new();
+
+    for start in start_dirs {
+        let mut chain = Vec::new();
+        for dir in start.ancestors() {
+            let candidate = dir.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                chain.push(fs::canonicalize(&candidate).unwrap_or(candidate));
+            }
+            if VCS_MARKERS.iter().any(|marker| dir.join(marker).exists()) {
+                break;
+            }
+        }
+
+        for path in chain.into_iter().rev() {
+            if !found.contains(&path) {
+                found.push(path);
+            }
+        }
+    }
+
+    found
+}
+
+/// Deep-merge `overlay` into `base`: mappings merge key by key, everything else is replaced
+pub(crate) fn merge_yaml(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(base_map), serde_yaml::Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -203,6 +653,9 @@ mod tests {
         assert!(dep_names.contains(&"serde_yaml"));
         assert!(dep_names.contains(&"dirs"));
         assert!(dep_names.contains(&"colored"));
+
+        let dev_dep_names: Vec<&str> = template.dev_dependencies.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(dev_dep_names, ["tempfile", "assert_cmd", "predicates"]);
     }
 
     #[test]
@@ -221,6 +674,105 @@ mod tests {
         assert!(serde_dep.features.contains(&"derive".to_string()));
     }
 
+    #[test]
+    fn test_resolved_dependencies_follow_logging_backend() {
+        let mut template = TemplateConfig::default();
+        let names = |template: &TemplateConfig| -> Vec<String> {
+            template
+                .resolved_dependencies()
+                .into_iter()
+                .map(|dep| dep.name)
+                .collect()
+        };
+
+        assert!(names(&template).contains(&"env_logger".to_string()));
+
+        template.logging = LoggingBackend::Fern;
+        let fern = names(&template);
+        assert!(fern.contains(&"fern".to_string()));
+        assert!(fern.contains(&"humantime".to_string()));
+        assert!(!fern.contains(&"env_logger".to_string()));
+        assert!(fern.contains(&"log".to_string()));
+
+        template.logging = LoggingBackend::Tracing;
+        let tracing = template.resolved_dependencies();
+        let subscriber = tracing.iter().find(|dep| dep.name == "tracing-subscriber").unwrap();
+        assert_eq!(subscriber.features, ["env-filter"]);
+        assert!(!tracing.iter().any(|dep| dep.name == "env_logger" || dep.name == "fern"));
+    }
+
+    #[test]
+    fn test_resolved_dependencies_follow_config_format() {
+        let mut template = TemplateConfig::default();
+        let has =
+            |template: &TemplateConfig, name: &str| template.resolved_dependencies().iter().any(|dep| dep.name == name);
+
+        assert!(has(&template, "serde_yaml"));
+
+        template.config_format = ConfigFormat::Toml;
+        assert!(has(&template, "toml"));
+        assert!(!has(&template, "serde_yaml"));
+        assert!(has(&template, "serde"));
+
+        template.config_format = ConfigFormat::Json;
+        assert!(has(&template, "serde_json"));
+        assert!(!has(&template, "toml"));
+        assert_eq!(ConfigFormat::Json.extension(), "json");
+    }
+
+    #[test]
+    fn test_resolved_dev_dependencies_follow_components() {
+        let mut template = TemplateConfig::default();
+        let has = |template: &TemplateConfig, name: &str| {
+            template.resolved_dev_dependencies().iter().any(|dep| dep.name == name)
+        };
+
+        assert!(has(&template, "assert_cmd"));
+        assert!(!has(&template, "criterion"));
+        assert!(!template.has_component(Component::Bench));
+
+        template.components.push(Component::Bench);
+        assert!(has(&template, "criterion"));
+        assert!(has(&template, "tempfile"));
+        assert!(template.has_component(Component::Bench));
+
+        template.components.push(Component::Testing);
+        assert!(has(&template, "proptest"));
+    }
+
+    #[test]
+    fn test_check_components_requires_lib() {
+        let mut template = TemplateConfig::default();
+        assert!(template.check_components().is_ok());
+
+        template.components.push(Component::Bench);
+        let err = template.check_components().unwrap_err().to_string();
+        assert!(err.contains("bench component needs a lib target"));
+
+        template.components = vec![Component::Testing];
+        let err = template.check_components().unwrap_err().to_string();
+        assert!(err.contains("testing component needs a lib target"));
+        assert!(err.contains("--lib"));
+
+        template.components.push(Component::Bench);
+        template.create_lib = true;
+        assert!(template.check_components().is_ok());
+    }
+
+    #[test]
+    fn test_resolved_dependencies_add_completion_crates() {
+        let mut template = TemplateConfig::default();
+        let has =
+            |template: &TemplateConfig, name: &str| template.resolved_dependencies().iter().any(|dep| dep.name == name);
+
+        assert!(!has(&template, "clap_complete"));
+        assert!(!has(&template, "clap_mangen"));
+
+        template.create_completions = true;
+        assert!(has(&template, "clap_complete"));
+        assert!(has(&template, "clap_mangen"));
+    }
+
     #[test]
     fn test_template_config_default_sample_config() {
         let template = TemplateConfig::default();
@@ -290,7 +842,7 @@ template:
 
         fs::write(&config_file, config_content).unwrap();
 
-        let config = Config::load(Some(&config_file)).unwrap();
+        let config = Config::load(Some(&config_file), None, true).unwrap();
 
         assert_eq!(config.default_author, "Test Author <test@example.com>");
         assert_eq!(config.default_license, "Apache-2.0");
@@ -318,7 +870,7 @@ template:
     fn test_config_load_from_nonexistent_file_returns_error() {
         let nonexistent_file = PathBuf::from("/this/file/does/not/exist.yml");
 
-        let result = Config::load(Some(&nonexistent_file));
+        let result = Config::load(Some(&nonexistent_file), None, true);
         assert!(result.is_err());
     }
 
@@ -330,7 +882,7 @@ template:
         let invalid_content = "invalid: yaml: content: [";
         fs::write(&config_file, invalid_content).unwrap();
 
-        let result = Config::load(Some(&config_file));
+        let result = Config::load(Some(&config_file), None, true);
         assert!(result.is_err());
     }
 
@@ -360,6 +912,305 @@ template:
         assert!(dep.features.is_empty());
     }
 
+    #[test]
+    fn test_discover_config_files_stops_at_vcs_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let repo = root.join("repo");
+        let nested = repo.join("crates").join("tool");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join("scaffold.yml"), "debug: true").unwrap();
+        fs::write(repo.join("scaffold.yml"), "debug: true").unwrap();
+        fs::write(nested.join("scaffold.yml"), "debug: true").unwrap();
+
+        let found = discover_config_files(std::slice::from_ref(&nested));
+
+        assert_eq!(found, vec![repo.join("scaffold.yml"), nested.join("scaffold.yml")]);
+    }
+
+    #[test]
+    fn test_discover_config_files_walks_from_nonexistent_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(repo.join("scaffold.yml"), "debug: true").unwrap();
+
+        let found = discover_config_files(&[repo.join("not-yet").join("created")]);
+
+        assert_eq!(found, vec![repo.join("scaffold.yml")]);
+    }
+
+    #[test]
+    fn test_discover_config_files_deduplicates_across_start_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().canonicalize().unwrap();
+        let sub = repo.join("sub");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(repo.join("scaffold.yml"), "debug: true").unwrap();
+        fs::write(sub.join("scaffold.yml"), "debug: true").unwrap();
+
+        let found = discover_config_files(&[repo.clone(), sub.join("new-project")]);
+
+        assert_eq!(found, vec![repo.join("scaffold.yml"), sub.join("scaffold.yml")]);
+    }
+
+    #[test]
+    fn test_load_layers_most_specific_wins() {
+        let temp_dir = TempDir::new().unwrap();
+        let team = temp_dir.path().join("team.yml");
+        let local = temp_dir.path().join("local.yml");
+        fs::write(
+            &team,
+            "default-author: \"Team <team@example.com>\"\ndefault-license: Apache-2.0\ntemplate:\n  create-build-rs: false",
+        )
+        .unwrap();
+        fs::write(&local, "default-author: \"Local <local@example.com>\"").unwrap();
+
+        let config = Config::load_layers(&[team, local], None, false).unwrap();
+
+        assert_eq!(config.default_author, "Local <local@example.com>");
+        assert_eq!(config.default_license, "Apache-2.0");
+        assert!(!config.template.create_build_rs);
+        assert!(config.template.create_cli_module);
+        assert_eq!(config.sources.len(), 2);
+    }
+
+    #[test]
+    fn test_load_layers_skips_invalid_and_empty_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let valid = temp_dir.path().join("valid.yml");
+        let invalid = temp_dir.path().join("invalid.yml");
+        let empty = temp_dir.path().join("empty.yml");
+        fs::write(&valid, "default-license: GPL-3.0").unwrap();
+        fs::write(&invalid, "debug: [not, a, bool]").unwrap();
+        fs::write(&empty, "").unwrap();
+
+        let config = Config::load_layers(&[valid, invalid, empty], None, false).unwrap();
+
+        assert_eq!(config.default_license, "GPL-3.0");
+        assert!(!config.debug);
+    }
+
+    #[test]
+    fn test_load_layers_project_layer_cannot_enable_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let user = temp_dir.path().join("user.yml");
+        let project = temp_dir.path().join("scaffold.yml");
+        fs::write(&user, "default-license: MIT").unwrap();
+        fs::write(&project, "expand-commands: true\ndefault-author: \"$(echo Someone)\"").unwrap();
+
+        let err = Config::load_layers(&[user.clone(), project], Some(&user), true).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("Command expansion is disabled"));
+    }
+
+    #[test]
+    fn test_load_layers_user_config_commands_stay_out_of_project_layers() {
+        let temp_dir = TempDir::new().unwrap();
+        let user = temp_dir.path().join("user.yml");
+        let project = temp_dir.path().join("scaffold.yml");
+        fs::write(&user, "expand-commands: true\ndefault-author: \"$(echo Someone)\"").unwrap();
+        fs::write(&project, "default-license: \"$(echo GPL-3.0)\"").unwrap();
+
+        assert!(Config::load_layers(&[user.clone(), project.clone()], Some(&user), true).is_err());
+
+        fs::write(&project, "expand-commands: false\ndefault-license: Apache-2.0").unwrap();
+        let config = Config::load_layers(&[user.clone(), project], Some(&user), true).unwrap();
+        assert_eq!(config.default_author, "Someone");
+        assert_eq!(config.default_license, "Apache-2.0");
+        assert!(config.expand_commands);
+    }
+
+    #[test]
+    fn test_load_layers_with_no_files_returns_default() {
+        let config = Config::load_layers(&[], None, false).unwrap();
+
+        assert_eq!(config.default_author, "Your Name <your.email@example.com>");
+    }
+
+    #[test]
+    fn test_config_load_expands_env_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("env.yml");
+        fs::write(
+            &config_file,
+            "default-author: \"${SCAFFOLD_TEST_SURELY_UNSET:-Team Member} <team@example.com>\"",
+        )
+        .unwrap();
+
+        let config = Config::load(Some(&config_file), None, true).unwrap();
+
+        assert_eq!(config.default_author, "Team Member <team@example.com>");
+    }
+
+    #[test]
+    fn test_config_load_conflict_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("scaffold.yml");
+        fs::write(
+            &config_path,
+            "on-conflict: backup\nconflict-rules:\n  - path: README.md\n    policy: skip\n",
+        )
+        .unwrap();
+
+        let config = Config::load(Some(&config_path), None, false).unwrap();
+
+        assert_eq!(config.on_conflict, Some(ConflictPolicy::Backup));
+        assert_eq!(config.conflict_rules.len(), 1);
+        assert_eq!(config.conflict_rules[0].path, "README.md");
+        assert_eq!(config.conflict_rules[0].policy, ConflictPolicy::Skip);
+    }
+
+    #[test]
+    fn test_config_load_command_expansion_requires_opt_in() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("cmd.yml");
+        fs::write(&config_file, "default-author: \"$(echo Someone)\"").unwrap();
+
+        let err = Config::load(Some(&config_file), None, true).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("Command expansion is disabled"));
+    }
+
+    #[test]
+    fn test_config_load_command_expansion_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("cmd.yml");
+        fs::write(
+            &config_file,
+            "expand-commands: true\ndefault-author: \"$(echo Someone)\"",
+        )
+        .unwrap();
+
+        let config = Config::load(Some(&config_file), None, true).unwrap();
+
+        assert_eq!(config.default_author, "Someone");
+    }
+
+    #[test]
+    fn test_config_load_command_expansion_disabled_by_caller() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("cmd.yml");
+        fs::write(
+            &config_file,
+            "expand-commands: true\ndefault-author: \"$(echo Someone)\"",
+        )
+        .unwrap();
+
+        let result = Config::load(Some(&config_file), None, false);
+
+        assert!(result.is_err());
+    }
+
+    const PROFILES_CONFIG: &str = r#"
+default-author: "Base Author <base@example.com>"
+default-license: MIT
+template:
+  create-build-rs: true
+  dependencies:
+    - name: clap
+      features: [derive]
+    - name: eyre
+profiles:
+  work:
+    default-author: "Work Author <me@work.com>"
+    default-license: Apache-2.0
+    template:
+      dependencies:
+        - name: anyhow
+  personal:
+    default-author: "Personal Author <me@home.net>"
+"#;
+
+    #[test]
+    fn test_config_with_profile_overlays_base() {
+        let config: Config = serde_yaml::from_str(PROFILES_CONFIG).unwrap();
+
+        let config = config.with_profile(Some("work")).unwrap();
+
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+        assert_eq!(config.default_author, "Work Author <me@work.com>");
+        assert_eq!(config.default_license, "Apache-2.0");
+        // Nested mappings merge, sequences replace
+        assert!(config.template.create_build_rs);
+        assert_eq!(config.template.dependencies.len(), 1);
+        assert_eq!(config.template.dependencies[0].name, "anyhow");
+    }
+
+    #[test]
+    fn test_config_with_profile_keeps_unset_base_values() {
+        let config: Config = serde_yaml::from_str(PROFILES_CONFIG).unwrap();
+
+        let config = config.with_profile(Some("personal")).unwrap();
+
+        assert_eq!(config.default_author, "Personal Author <me@home.net>");
+        assert_eq!(config.default_license, "MIT");
+        assert_eq!(config.template.dependencies.len(), 2);
+    }
+
+    #[test]
+    fn test_config_with_profile_uses_default_profile() {
+        let mut config: Config = serde_yaml::from_str(PROFILES_CONFIG).unwrap();
+        config.default_profile = Some("personal".to_string());
+
+        let config = config.with_profile(None).unwrap();
+
+        assert_eq!(config.active_profile.as_deref(), Some("personal"));
+        assert_eq!(config.default_author, "Personal Author <me@home.net>");
+    }
+
+    #[test]
+    fn test_config_with_profile_flag_overrides_default_profile() {
+        let mut config: Config = serde_yaml::from_str(PROFILES_CONFIG).unwrap();
+        config.default_profile = Some("personal".to_string());
+
+        let config = config.with_profile(Some("work")).unwrap();
+
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_config_with_profile_none_returns_base() {
+        let config: Config = serde_yaml::from_str(PROFILES_CONFIG).unwrap();
+
+        let config = config.with_profile(None).unwrap();
+
+        assert!(config.active_profile.is_none());
+        assert_eq!(config.default_author, "Base Author <base@example.com>");
+    }
+
+    #[test]
+    fn test_config_with_unknown_profile_returns_error() {
+        let config: Config = serde_yaml::from_str(PROFILES_CONFIG).unwrap();
+
+        let err = config.with_profile(Some("missing")).unwrap_err().to_string();
+
+        assert!(err.contains("Unknown profile 'missing'"));
+        assert!(err.contains("personal, work"));
+    }
+
+    #[test]
+    fn test_config_json_schema_uses_kebab_case_keys() {
+        let schema: serde_json::Value = serde_json::from_str(&Config::json_schema().unwrap()).unwrap();
+
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("default-author"));
+        assert!(properties.contains_key("create-git-repo"));
+        assert!(properties.contains_key("template"));
+        assert!(!properties.contains_key("default_author"));
+    }
+
+    #[test]
+    fn test_config_json_schema_includes_doc_comments() {
+        let schema = Config::json_schema().unwrap();
+
+        assert!(schema.contains("Configuration for the scaffold tool"));
+        assert!(schema.contains("Cargo features to enable"));
+        assert!(schema.contains("after-help"));
+    }
+
     #[test]
     fn test_config_serialization_roundtrip() {
         let original_config = Config::default();
@@ -0,0 +1,373 @@
+use clap::ValueEnum;
+use eyre::{Context, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// What to do when a generated file already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Keep the existing file
+    Skip,
+    /// Replace the existing file
+    Overwrite,
+    /// Copy the existing file to <file>.orig (or the first free <file>.orig.N), then replace it
+    Backup,
+    /// Ask for each conflicting file
+    Prompt,
+    /// Merge TOML/YAML keeping the existing file's comments (existing values win); other files get conflict markers
+    Merge,
+}
+
+/// Conflict policy for files matching a glob pattern, relative to the project root
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ConflictRule {
+    /// Glob pattern, e.g. "README.md" or ".github/workflows/*"
+    pub path: String,
+    /// Policy applied to matching files
+    pub policy: ConflictPolicy,
+}
+
+/// Picks the conflict policy for each file: the first matching rule, else the default
+#[derive(Debug)]
+pub struct ConflictResolver {
+    default: ConflictPolicy,
+    rules: Vec<(GlobMatcher, ConflictPolicy)>,
+}
+
+impl Default for ConflictResolver {
+    fn default() -> Self {
+        Self {
+            default: ConflictPolicy::Overwrite,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl ConflictResolver {
+    pub fn new(default: ConflictPolicy, rules: &[ConflictRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                // `*` stays within one path component; use `**` to match across directories
+                let glob = GlobBuilder::new(&rule.path)
+                    .literal_separator(true)
+                    .build()
+                    .context(format!("Invalid conflict rule pattern: {}", rule.path))?;
+                Ok((glob.compile_matcher(), rule.policy))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { default, rules })
+    }
+
+    /// Policy for a path relative to the project root
+    pub fn policy_for(&self, relative: &Path) -> ConflictPolicy {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(relative))
+            .map(|(_, policy)| *policy)
+            .unwrap_or(self.default)
+    }
+}
+
+/// Ask on the terminal how to resolve a conflict; skips when stdin is not interactive
+pub fn prompt_policy(display: &str) -> Result<ConflictPolicy> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        log::warn!("Cannot prompt for {} without a terminal, skipping", display);
+        return Ok(ConflictPolicy::Skip);
+    }
+
+    loop {
+        print!("{} already exists: [o]verwrite, [s]kip, [b]ackup, [m]erge? ", display);
+        std::io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut answer = String::new();
+        let read = stdin.lock().read_line(&mut answer).context("Failed to read answer")?;
+        // Ctrl-C only takes effect once read_line returns; don't ask again after it
+        crate::transaction::check_interrupted()?;
+        if read == 0 {
+            return Ok(ConflictPolicy::Skip);
+        }
+        if let Some(policy) = parse_answer(&answer) {
+            return Ok(policy);
+        }
+    }
+}
+
+fn parse_answer(answer: &str) -> Option<ConflictPolicy> {
+    match answer.trim().to_lowercase().as_str() {
+        "o" | "overwrite" => Some(ConflictPolicy::Overwrite),
+        "s" | "skip" | "" => Some(ConflictPolicy::Skip),
+        "b" | "backup" => Some(ConflictPolicy::Backup),
+        "m" | "merge" => Some(ConflictPolicy::Merge),
+        _ => None,
+    }
+}
+
+/// Result of merging generated content into an existing file
+#[derive(Debug, PartialEq, Eq)]
+pub enum Merged {
+    /// Structurally merged, no manual work needed
+    Clean(String),
+    /// Contains conflict markers to resolve by hand
+    Conflicted(String),
+}
+
+/// Merge generated content into an existing file; existing values win
+pub fn merge(path: &Path, existing: &str, generated: &str) -> Merged {
+    if existing == generated {
+        return Merged::Clean(existing.to_string());
+    }
+
+    let merged = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => merge_toml(existing, generated),
+        Some("yml" | "yaml") => merge_yaml_files(existing, generated),
+        _ => None,
+    };
+
+    merged
+        .map(Merged::Clean)
+        .unwrap_or_else(|| Merged::Conflicted(conflict_markers(existing, generated)))
+}
+
+fn merge_toml(existing: &str, generated: &str) -> Option<String> {
+    let mut existing: toml_edit::DocumentMut = existing.parse().ok()?;
+    let generated: toml_edit::DocumentMut = generated.parse().ok()?;
+
+    merge_toml_tables(existing.as_table_mut(), generated.as_table());
+    Some(existing.to_string())
+}
+
+/// Add keys missing from `existing`, recursing into tables present in both
+fn merge_toml_tables(existing: &mut toml_edit::Table, generated: &toml_edit::Table) {
+    for (key, item) in generated.iter() {
+        match (existing.get_mut(key), item) {
+            (None, _) => {
+                existing.insert(key, item.clone());
+            }
+            (Some(toml_edit::Item::Table(existing_table)), toml_edit::Item::Table(generated_table)) => {
+                merge_toml_tables(existing_table, generated_table);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Merge YAML without re-serializing the existing file, so its comments and layout survive
+///
+/// Generated top-level keys the file lacks are appended to it; keys missing further down can't be
+/// added without rewriting the file, so those merges fall back to conflict markers.
+fn merge_yaml_files(existing: &str, generated: &str) -> Option<String> {
+    let existing_value: serde_yaml::Value = serde_yaml::from_str(existing).ok()?;
+    let generated_value: serde_yaml::Value = serde_yaml::from_str(generated).ok()?;
+    let existing_map = existing_value.as_mapping()?;
+    let generated_map = generated_value.as_mapping()?;
+
+    let mut missing = serde_yaml::Mapping::new();
+    for (key, value) in generated_map {
+        match existing_map.get(key) {
+            None => {
+                missing.insert(key.clone(), value.clone());
+            }
+            Some(existing_child) if !covers_yaml(existing_child, value) => return None,
+            Some(_) => {}
+        }
+    }
+
+    let mut merged = existing.to_string();
+    if !missing.is_empty() {
+        if !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged.push_str(&serde_yaml::to_string(&missing).ok()?);
+    }
+    Some(merged)
+}
+
+/// Whether `existing` has every mapping key of `generated`; other values are kept as they are
+fn covers_yaml(existing: &serde_yaml::Value, generated: &serde_yaml::Value) -> bool {
+    match (existing, generated) {
+        (serde_yaml::Value::Mapping(existing), serde_yaml::Value::Mapping(generated)) => {
+            generated.iter().all(|(key, value)| {
+                existing
+                    .get(key)
+                    .is_some_and(|existing_value| covers_yaml(existing_value, value))
+            })
+        }
+        _ => true,
+    }
+}
+
+/// `<path>.orig`, or `<path>.orig.N` with the first N not taken, so earlier backups are never replaced
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut base = path.as_os_str().to_owned();
+    base.push(".orig");
+    let base = PathBuf::from(base);
+
+    let mut candidate = base.clone();
+    let mut index = 1;
+    while candidate.exists() {
+        let mut numbered = base.as_os_str().to_owned();
+        numbered.push(format!(".{}", index));
+        candidate = PathBuf::from(numbered);
+        index += 1;
+    }
+    candidate
+}
+
+fn conflict_markers(existing: &str, generated: &str) -> String {
+    let mut out = String::from("<<<<<<< existing\n");
+    out.push_str(existing);
+    if !existing.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str("=======\n");
+    out.push_str(generated);
+    if !generated.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(">>>>>>> scaffold\n");
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn rule(path: &str, policy: ConflictPolicy) -> ConflictRule {
+        ConflictRule {
+            path: path.to_string(),
+            policy,
+        }
+    }
+
+    #[test]
+    fn test_resolver_uses_first_matching_rule() {
+        let resolver = ConflictResolver::new(
+            ConflictPolicy::Backup,
+            &[
+                rule("README.md", ConflictPolicy::Skip),
+                rule(".github/workflows/*", ConflictPolicy::Overwrite),
+                rule(".github/**", ConflictPolicy::Skip),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(resolver.policy_for(Path::new("README.md")), ConflictPolicy::Skip);
+        assert_eq!(
+            resolver.policy_for(Path::new(".github/workflows/ci.yml")),
+            ConflictPolicy::Overwrite
+        );
+        assert_eq!(
+            resolver.policy_for(Path::new(".github/CODEOWNERS")),
+            ConflictPolicy::Skip
+        );
+        assert_eq!(
+            resolver.policy_for(Path::new(".github/workflows/nested/x.yml")),
+            ConflictPolicy::Skip
+        );
+        assert_eq!(resolver.policy_for(Path::new("src/main.rs")), ConflictPolicy::Backup);
+    }
+
+    #[test]
+    fn test_resolver_rejects_invalid_pattern() {
+        let result = ConflictResolver::new(ConflictPolicy::Skip, &[rule("[unclosed", ConflictPolicy::Skip)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("o\n"), Some(ConflictPolicy::Overwrite));
+        assert_eq!(parse_answer("Backup"), Some(ConflictPolicy::Backup));
+        assert_eq!(parse_answer("\n"), Some(ConflictPolicy::Skip));
+        assert_eq!(parse_answer("x"), None);
+    }
+
+    #[test]
+    fn test_merge_toml_keeps_existing_values_and_formatting() {
+        let existing = "[package]\nname = \"mine\"   # keep me\n\n[dependencies]\nserde = \"1\"\n";
+        let generated = "[package]\nname = \"generated\"\nedition = \"2024\"\n\n[dependencies]\nclap = \"*\"\n\n[build-dependencies]\n";
+
+        let Merged::Clean(merged) = merge(Path::new("Cargo.toml"), existing, generated) else {
+            panic!("expected a clean merge");
+        };
+
+        assert!(merged.contains("name = \"mine\"   # keep me"));
+        assert!(merged.contains("edition = \"2024\""));
+        assert!(merged.contains("serde = \"1\""));
+        assert!(merged.contains("clap = \"*\""));
+        assert!(merged.contains("[build-dependencies]"));
+    }
+
+    #[test]
+    fn test_merge_yaml_keeps_existing_values_and_comments() {
+        let existing = "# team settings\nname: Mine # keep me\ntasks:\n  test:\n    help: custom\n";
+        let generated = "name: Generated\nage: 30\ntasks:\n  test:\n    help: default\n";
+
+        let Merged::Clean(merged) = merge(Path::new(".otto.yml"), existing, generated) else {
+            panic!("expected a clean merge");
+        };
+        let value: serde_yaml::Value = serde_yaml::from_str(&merged).unwrap();
+
+        assert!(merged.starts_with(existing));
+        assert_eq!(value["name"].as_str(), Some("Mine"));
+        assert_eq!(value["age"].as_u64(), Some(30));
+        assert_eq!(value["tasks"]["test"]["help"].as_str(), Some("custom"));
+    }
+
+    #[test]
+    fn test_merge_yaml_with_nested_additions_falls_back_to_markers() {
+        let existing = "# team settings\ntasks:\n  test:\n    help: custom\n";
+        let generated = "tasks:\n  test:\n    help: default\n  build:\n    help: build\n";
+
+        let Merged::Conflicted(merged) = merge(Path::new(".otto.yml"), existing, generated) else {
+            panic!("expected conflict markers");
+        };
+
+        assert!(merged.contains("# team settings"));
+        assert!(merged.contains(">>>>>>> scaffold"));
+    }
+
+    #[test]
+    fn test_backup_path_skips_existing_backups() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("ci.yml");
+
+        assert_eq!(backup_path(&file), temp_dir.path().join("ci.yml.orig"));
+        std::fs::write(temp_dir.path().join("ci.yml.orig"), "first").unwrap();
+        std::fs::write(temp_dir.path().join("ci.yml.orig.1"), "second").unwrap();
+        assert_eq!(backup_path(&file), temp_dir.path().join("ci.yml.orig.2"));
+    }
+
+    #[test]
+    fn test_merge_other_files_writes_conflict_markers() {
+        let merged = merge(Path::new("src/main.rs"), "fn main() {}", "fn main() {\n}\n");
+
+        assert_eq!(
+            merged,
+            Merged::Conflicted(
+                "<<<<<<< existing\nfn main() {}\n=======\nfn main() {\n}\n>>>>>>> scaffold\n".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_identical_content_is_clean() {
+        assert_eq!(
+            merge(Path::new("notes.txt"), "same\n", "same\n"),
+            Merged::Clean("same\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_unparseable_toml_falls_back_to_markers() {
+        let merged = merge(Path::new("Cargo.toml"), "not = [valid", "[package]\n");
+        assert!(matches!(merged, Merged::Conflicted(_)));
+    }
+}
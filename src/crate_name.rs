@@ -0,0 +1,379 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Longest package name crates.io accepts
+const MAX_LENGTH: usize = 64;
+
+/// Strict and reserved Rust keywords; cargo refuses these as package names
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+    "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true",
+    "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Crates that ship with Rust; a package with the same crate name shadows them
+const BUILTIN_CRATES: &[&str] = &["alloc", "core", "proc_macro", "std", "test"];
+
+/// Directory names cargo uses inside target/ for build artifacts
+const ARTIFACT_DIRS: &[&str] = &["build", "deps", "examples", "incremental"];
+
+/// Device names Windows cannot use as file names
+const WINDOWS_RESERVED: &[&str] = &[
+    "aux", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "con", "lpt1", "lpt2", "lpt3",
+    "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9", "nul", "prn",
+];
+
+/// Check a project name against cargo's package-name rules, suggesting a fix when there is one
+pub fn validate(name: &str) -> eyre::Result<()> {
+    let problem = match find_problem(name) {
+        Some(problem) => problem,
+        None => return Ok(()),
+    };
+
+    match suggest(name) {
+        Some(suggestion) => Err(eyre::eyre!("{} (try '{}')", problem, suggestion)),
+        None => Err(eyre::eyre!("{}", problem)),
+    }
+}
+
+fn find_problem(name: &str) -> Option<String> {
+    if name.is_empty() {
+        return Some("Project name cannot be empty".to_string());
+    }
+
+    if name.starts_with('-') || name.starts_with('_') {
+        return Some("Project name cannot start with '-' or '_' (these look like CLI flags)".to_string());
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Some(
+            "Project name must contain only alphanumeric characters (ASCII), hyphens, and underscores".to_string(),
+        );
+    }
+
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Some("Project name cannot start with a digit".to_string());
+    }
+
+    if name.len() > MAX_LENGTH {
+        return Some(format!(
+            "Project name is {} characters long, crates.io allows at most {}",
+            name.len(),
+            MAX_LENGTH
+        ));
+    }
+
+    reserved_reason(name).map(|reason| format!("Project name '{}' {}", name, reason))
+}
+
+/// Whether `name` is a strict or reserved Rust keyword and so can't be used as an identifier
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Why a syntactically valid name is still unusable, if it is
+fn reserved_reason(name: &str) -> Option<&'static str> {
+    let lower = name.to_ascii_lowercase();
+    let crate_name = lower.replace('-', "_");
+
+    if is_keyword(&lower) {
+        Some("is a Rust keyword")
+    } else if BUILTIN_CRATES.contains(&crate_name.as_str()) {
+        Some("conflicts with a crate built into Rust")
+    } else if ARTIFACT_DIRS.contains(&lower.as_str()) {
+        Some("conflicts with cargo's build directories")
+    } else if WINDOWS_RESERVED.contains(&lower.as_str()) {
+        Some("is a reserved file name on Windows")
+    } else {
+        None
+    }
+}
+
+/// Closest valid kebab-case name, or None if nothing usable remains or the name is already canonical
+fn suggest(name: &str) -> Option<String> {
+    let mut suggestion = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            suggestion.push(c.to_ascii_lowercase());
+        } else if !suggestion.is_empty() && !suggestion.ends_with('-') {
+            suggestion.push('-');
+        }
+    }
+
+    let mut suggestion = suggestion
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '-')
+        .to_string();
+    suggestion.truncate(MAX_LENGTH);
+    let mut suggestion = suggestion.trim_end_matches('-').to_string();
+
+    if suggestion.is_empty() {
+        return None;
+    }
+
+    if reserved_reason(&suggestion).is_some() {
+        suggestion.push_str("-cli");
+    }
+
+    (suggestion != name).then_some(suggestion)
+}
+
+/// Non-fatal issues: names cargo accepts but warns about, and clashes with installed binaries
+pub fn warnings(name: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if name.chars().any(|c| c.is_ascii_uppercase()) || (name.contains('_') && name.contains('-')) {
+        let mut warning = format!("Project name '{}' is not kebab-case or snake_case", name);
+        if let Some(suggestion) = suggest(name) {
+            warning.push_str(&format!(", consider '{}'", suggestion));
+        }
+        warnings.push(warning);
+    }
+
+    if let Some(path) = find_on_path(name) {
+        warnings.push(format!(
+            "A '{}' binary already exists at {}; whichever comes first on PATH will win",
+            name,
+            path.display()
+        ));
+    }
+
+    warnings
+}
+
+/// Where an inferred project name came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameSource {
+    GitRemote,
+    Directory,
+}
+
+impl fmt::Display for NameSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            Self::GitRemote => "git remote origin",
+            Self::Directory => "directory name",
+        };
+        write!(f, "{}", source)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferredName {
+    pub name: String,
+    pub source: NameSource,
+}
+
+/// Infer a project name for `dir` from its origin remote, then its directory name,
+/// normalized to kebab-case
+pub fn infer(dir: &Path) -> Option<InferredName> {
+    let from_remote = origin_url(dir)
+        .and_then(|url| repo_name_from_url(&url))
+        .map(|name| (name, NameSource::GitRemote));
+    let from_dir = || {
+        std::path::absolute(dir)
+            .ok()?
+            .file_name()
+            .map(|name| (name.to_string_lossy().to_string(), NameSource::Directory))
+    };
+
+    let (raw, source) = from_remote.or_else(from_dir)?;
+    let name = suggest(&raw).unwrap_or(raw);
+
+    Some(InferredName { name, source })
+}
+
+fn origin_url(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Last path segment of a remote URL without `.git`, for https, ssh and scp-style URLs
+fn repo_name_from_url(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+
+    url.rsplit(['/', ':'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    find_in_dirs(name, std::env::split_paths(&path))
+}
+
+fn find_in_dirs(name: &str, dirs: impl IntoIterator<Item = PathBuf>) -> Option<PathBuf> {
+    dirs.into_iter()
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() || path.with_extension("exe").is_file()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_accepts_cargo_style_names() {
+        for name in ["valid", "valid-name", "valid_name", "valid123", "v", "tokio-rs2"] {
+            assert!(validate(name).is_ok(), "rejected {}", name);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_non_ascii_letters() {
+        let err = validate("café").unwrap_err().to_string();
+        assert!(err.contains("alphanumeric characters (ASCII)"));
+        assert!(err.contains("(try 'caf')"));
+    }
+
+    #[test]
+    fn test_validate_rejects_leading_digit() {
+        let err = validate("2fast").unwrap_err().to_string();
+        assert!(err.contains("cannot start with a digit"));
+        assert!(err.contains("(try 'fast')"));
+    }
+
+    #[test]
+    fn test_validate_rejects_keywords_and_reserved_names() {
+        let cases = [
+            ("fn", "is a Rust keyword"),
+            ("self", "is a Rust keyword"),
+            ("test", "built into Rust"),
+            ("proc-macro", "built into Rust"),
+            ("deps", "build directories"),
+            ("nul", "reserved file name on Windows"),
+        ];
+
+        for (name, reason) in cases {
+            let err = validate(name).unwrap_err().to_string();
+            assert!(err.contains(reason), "{}: {}", name, err);
+            assert!(err.contains(&format!("(try '{}-cli')", name)), "{}: {}", name, err);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_names_over_length_limit() {
+        let name = "a".repeat(MAX_LENGTH + 1);
+        let err = validate(&name).unwrap_err().to_string();
+        assert!(err.contains("at most 64"));
+        assert!(err.contains(&format!("(try '{}')", "a".repeat(MAX_LENGTH))));
+    }
+
+    #[test]
+    fn test_suggest_normalizes_to_kebab_case() {
+        assert_eq!(suggest("my_Tool"), Some("my-tool".to_string()));
+        assert_eq!(suggest("My Cool  App!"), Some("my-cool-app".to_string()));
+        assert_eq!(suggest("__init__"), Some("init".to_string()));
+        assert_eq!(suggest("my-tool"), None);
+        assert_eq!(suggest("@@@"), None);
+    }
+
+    #[test]
+    fn test_warnings_for_mixed_case_and_separators() {
+        assert_eq!(
+            warnings("my_Tool-scaffold-test"),
+            vec![
+                "Project name 'my_Tool-scaffold-test' is not kebab-case or snake_case, consider 'my-tool-scaffold-test'"
+            ]
+        );
+        assert!(warnings("my_tool_scaffold_test").is_empty());
+    }
+
+    #[test]
+    fn test_repo_name_from_url() {
+        assert_eq!(
+            repo_name_from_url("git@github.com:org/my-tool.git"),
+            Some("my-tool".to_string())
+        );
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/my-tool/"),
+            Some("my-tool".to_string())
+        );
+        assert_eq!(
+            repo_name_from_url("ssh://git@host:2222/org/my-tool.git\n"),
+            Some("my-tool".to_string())
+        );
+        assert_eq!(repo_name_from_url("host:repo"), Some("repo".to_string()));
+        assert_eq!(repo_name_from_url(""), None);
+    }
+
+    #[test]
+    fn test_infer_uses_git_remote_origin() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("checkout");
+        fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(&dir).output().unwrap();
+        };
+        git(&["init", "-q"]);
+        git(&["remote", "add", "origin", "git@github.com:org/Remote_Name.git"]);
+
+        assert_eq!(
+            infer(&dir),
+            Some(InferredName {
+                name: "remote-name".to_string(),
+                source: NameSource::GitRemote,
+            })
+        );
+    }
+
+    #[test]
+    fn test_infer_falls_back_to_directory_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("my-dir");
+        fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            infer(&dir),
+            Some(InferredName {
+                name: "my-dir".to_string(),
+                source: NameSource::Directory,
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_in_dirs_finds_executables_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let bin = temp_dir.path().join("mytool");
+        fs::write(&bin, "#!/bin/sh").unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(find_in_dirs("mytool", vec![temp_dir.path().to_path_buf()]), None);
+            fs::set_permissions(&bin, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        assert_eq!(find_in_dirs("mytool", vec![temp_dir.path().to_path_buf()]), Some(bin));
+        assert_eq!(find_in_dirs("other", vec![temp_dir.path().to_path_buf()]), None);
+    }
+}
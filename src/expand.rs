@@ -0,0 +1,239 @@
+use eyre::{Context, Result};
+use std::process::Command;
+
+/// Expand variable and command references in every string value of a YAML tree
+///
+/// Supported syntax:
+/// - `${NAME}`: environment variable, error if unset
+/// - `${NAME:-default}`: environment variable, `default` if unset or empty
+/// - `$(command)`: stdout of `sh -c command`, trimmed; only when `allow_commands` is true
+/// - `$$`: a literal `$`
+pub fn expand_yaml(value: &mut serde_yaml::Value, allow_commands: bool) -> Result<()> {
+    expand_yaml_at(value, allow_commands, "")
+}
+
+fn expand_yaml_at(value: &mut serde_yaml::Value, allow_commands: bool, path: &str) -> Result<()> {
+    match value {
+        serde_yaml::Value::String(s) if s.contains('$') => {
+            *s = expand_str(s, allow_commands).context(format!("Failed to expand value of '{}'", path))?;
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (key, child) in map.iter_mut() {
+                let key = key.as_str().map(str::to_string).unwrap_or_else(|| format!("{:?}", key));
+                let child_path = if path.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                expand_yaml_at(child, allow_commands, &child_path)?;
+            }
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            for (i, child) in seq.iter_mut().enumerate() {
+                expand_yaml_at(child, allow_commands, &format!("{}[{}]", path, i))?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Expand `${NAME}`, `${NAME:-default}`, `$(command)` and `$$` in a single string
+pub fn expand_str(input: &str, allow_commands: bool) -> Result<String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        if let Some(tail) = after.strip_prefix('$') {
+            out.push('$');
+            rest = tail;
+        } else if let Some(tail) = after.strip_prefix('{') {
+            let end = tail
+                .find('}')
+                .ok_or_else(|| eyre::eyre!("Unterminated '${{' in '{}'", input))?;
+            out.push_str(&expand_variable(&tail[..end])?);
+            rest = &tail[end + 1..];
+        } else if let Some(tail) = after.strip_prefix('(') {
+            let end = find_closing_paren(tail).ok_or_else(|| eyre::eyre!("Unterminated '$(' in '{}'", input))?;
+            let command = &tail[..end];
+            if !allow_commands {
+                return Err(eyre::eyre!(
+                    "Command expansion is disabled: $({}) (set expand-commands: true in the user config or a --config file to allow it)",
+                    command
+                ));
+            }
+            out.push_str(&run_command(command)?);
+            rest = &tail[end + 1..];
+        } else {
+            out.push('$');
+            rest = after;
+        }
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn expand_variable(expr: &str) -> Result<String> {
+    let (name, default) = match expr.split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (expr, None),
+    };
+
+    if !is_valid_variable_name(name) {
+        return Err(eyre::eyre!("Invalid environment variable name: '{}'", name));
+    }
+
+    match (std::env::var(name), default) {
+        (Ok(value), Some(default)) if value.is_empty() => Ok(default.to_string()),
+        (Ok(value), _) => Ok(value),
+        (Err(_), Some(default)) => Ok(default.to_string()),
+        (Err(_), None) => Err(eyre::eyre!(
+            "Environment variable {} is not set (use ${{{}:-default}} to provide a fallback)",
+            name,
+            name
+        )),
+    }
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Index of the `)` closing an already-opened `$(`, allowing nested parentheses
+fn find_closing_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn run_command(command: &str) -> Result<String> {
+    log::info!("Expanding command: {}", command);
+    let output = Command::new("sh")
+        .args(["-c", command])
+        .output()
+        .context(format!("Failed to run command: {}", command))?;
+
+    if !output.status.success() {
+        return Err(eyre::eyre!(
+            "Command failed ({}): {}: {}",
+            output.status,
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_str_without_references_is_unchanged() {
+        assert_eq!(
+            expand_str("Plain Author <a@b.c>", false).unwrap(),
+            "Plain Author <a@b.c>"
+        );
+    }
+
+    #[test]
+    fn test_expand_str_env_variable() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(expand_str("dir=${HOME}/x", false).unwrap(), format!("dir={}/x", home));
+    }
+
+    #[test]
+    fn test_expand_str_unset_variable_errors() {
+        let err = expand_str("${SCAFFOLD_TEST_SURELY_UNSET}", false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("SCAFFOLD_TEST_SURELY_UNSET is not set"));
+    }
+
+    #[test]
+    fn test_expand_str_default_for_unset_variable() {
+        assert_eq!(
+            expand_str("${SCAFFOLD_TEST_SURELY_UNSET:-Fallback Name}", false).unwrap(),
+            "Fallback Name"
+        );
+    }
+
+    #[test]
+    fn test_expand_str_escaped_dollar() {
+        assert_eq!(expand_str("cost: $$5", false).unwrap(), "cost: $5");
+        assert_eq!(expand_str("lone $ sign", false).unwrap(), "lone $ sign");
+    }
+
+    #[test]
+    fn test_expand_str_invalid_variable_name_errors() {
+        let err = expand_str("${1BAD}", false).unwrap_err().to_string();
+        assert!(err.contains("Invalid environment variable name: '1BAD'"));
+    }
+
+    #[test]
+    fn test_expand_str_unterminated_reference_errors() {
+        assert!(expand_str("${HOME", false).is_err());
+        assert!(expand_str("$(echo hi", true).is_err());
+    }
+
+    #[test]
+    fn test_expand_str_command_disabled_by_default() {
+        let err = expand_str("$(echo hi)", false).unwrap_err().to_string();
+        assert!(err.contains("Command expansion is disabled"));
+        assert!(err.contains("expand-commands"));
+    }
+
+    #[test]
+    fn test_expand_str_command_when_allowed() {
+        assert_eq!(
+            expand_str("<$(echo me@example.com)>", true).unwrap(),
+            "<me@example.com>"
+        );
+        assert_eq!(expand_str("$(echo $(echo nested))", true).unwrap(), "nested");
+    }
+
+    #[test]
+    fn test_expand_str_failing_command_errors() {
+        let err = expand_str("$(exit 3)", true).unwrap_err().to_string();
+        assert!(err.contains("Command failed"));
+    }
+
+    #[test]
+    fn test_expand_yaml_reports_key_path() {
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("template:\n  dependencies:\n    - name: ${SCAFFOLD_TEST_SURELY_UNSET}").unwrap();
+
+        let err = expand_yaml(&mut value, false).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("template.dependencies[0].name"));
+    }
+
+    #[test]
+    fn test_expand_yaml_expands_nested_strings_only() {
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("a: ${SCAFFOLD_TEST_SURELY_UNSET:-x}\nb:\n  - ${SCAFFOLD_TEST_SURELY_UNSET:-y}\nc: 3")
+                .unwrap();
+
+        expand_yaml(&mut value, false).unwrap();
+
+        assert_eq!(value["a"].as_str(), Some("x"));
+        assert_eq!(value["b"][0].as_str(), Some("y"));
+        assert_eq!(value["c"].as_u64(), Some(3));
+    }
+}
@@ -2,6 +2,14 @@
 #![deny(dead_code)]
 #![deny(unused_variables)]
 
+pub mod author;
 pub mod cli;
+pub mod cli_spec;
+pub mod completions;
 pub mod config;
+pub mod conflict;
+pub mod crate_name;
+pub mod expand;
 pub mod templates;
+pub mod transaction;
+pub mod workspace;
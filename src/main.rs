@@ -8,15 +8,24 @@ use eyre::{Context, Result};
 use log::{error, info, warn};
 
 use std::fs;
-use std::path::PathBuf;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+mod author;
 mod cli;
+mod cli_spec;
+mod completions;
 mod config;
+mod conflict;
+mod crate_name;
+mod expand;
 mod templates;
+mod transaction;
+mod workspace;
 
-use cli::Cli;
-use config::Config;
+use cli::{Cli, Command as CliCommand, ConfigAction};
+use config::{Config, ProjectKind};
 
 fn setup_logging() -> Result<()> {
     let log_dir = dirs::data_local_dir()
@@ -44,8 +53,11 @@ fn setup_logging() -> Result<()> {
     Ok(())
 }
 
-/// Check if a directory looks like a fresh cloned repo (only contains typical repo files)
-fn is_scaffoldable_directory(dir: &std::path::Path) -> Result<bool> {
+/// Entries that keep a directory from looking like a fresh cloned repo, sorted by name
+///
+/// Besides the typical repo files, `allowed` glob patterns from scaffold.yml are accepted;
+/// names and patterns are compared case-insensitively.
+fn blocking_entries(dir: &Path, allowed: &[String]) -> Result<Vec<String>> {
     const ALLOWED_FILES: &[&str] = &[
         ".git",
         ".gitignore",
@@ -71,95 +83,282 @@ fn is_scaffoldable_directory(dir: &std::path::Path) -> Result<bool> {
         "SECURITY.md",
     ];
 
+    let mut patterns = globset::GlobSetBuilder::new();
+    for pattern in allowed {
+        let glob = globset::GlobBuilder::new(pattern.trim_end_matches('/'))
+            .case_insensitive(true)
+            .literal_separator(true)
+            .build()
+            .context(format!("Invalid allowed-files pattern: {}", pattern))?;
+        patterns.add(glob);
+    }
+    let patterns = patterns.build().context("Failed to build allowed-files patterns")?;
+
+    let mut blocking = Vec::new();
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let file_name = entry.file_name();
         let name = file_name.to_string_lossy();
 
-        if !ALLOWED_FILES.iter().any(|&allowed| name.eq_ignore_ascii_case(allowed)) {
+        if !ALLOWED_FILES.iter().any(|&allowed| name.eq_ignore_ascii_case(allowed)) && !patterns.is_match(&*name) {
             info!("Directory contains non-repo file: {}, blocking scaffold", name);
-            return Ok(false);
+            blocking.push(name.to_string());
         }
     }
 
-    Ok(true)
+    blocking.sort();
+    Ok(blocking)
 }
 
-fn create_project(cli: &Cli, config: &Config) -> Result<()> {
-    let project = &cli.project;
-    let default_dir = PathBuf::from(project);
-    let target_dir = cli.directory.as_ref().unwrap_or(&default_dir);
+/// Project name given on the command line; `.` means "scaffold here" rather than a name
+///
+/// A path such as `./man` names the project after its last component, so projects can share a
+/// subcommand's name.
+fn explicit_project(cli: &Cli) -> Option<&str> {
+    cli.project.as_deref().filter(|project| *project != ".").map(|project| {
+        Path::new(project)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(project)
+    })
+}
 
-    if project.is_empty() {
-        return Err(eyre::eyre!("Project name cannot be empty"));
+/// Directory to scaffold into: --directory, else ./<project>, else the current directory
+fn target_dir(cli: &Cli) -> PathBuf {
+    match (&cli.directory, cli.project.as_deref()) {
+        (Some(dir), _) => dir.clone(),
+        (None, Some(project)) => PathBuf::from(project),
+        (None, None) => PathBuf::from("."),
     }
+}
 
-    if project.starts_with('-') || project.starts_with('_') {
-        return Err(eyre::eyre!(
-            "Project name cannot start with '-' or '_' (these look like CLI flags)"
-        ));
+/// The project name from the command line, or one inferred from the target directory and confirmed
+fn project_name(cli: &Cli, target_dir: &Path) -> Result<String> {
+    if let Some(project) = explicit_project(cli) {
+        return Ok(project.to_string());
     }
 
-    if !project.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
-        return Err(eyre::eyre!(
-            "Project name must contain only alphanumeric characters, hyphens, and underscores"
-        ));
+    let inferred = crate_name::infer(target_dir).ok_or_else(|| {
+        eyre::eyre!(
+            "Could not infer a project name for {}, pass one explicitly",
+            target_dir.display()
+        )
+    })?;
+    info!("Inferred project name {} (from {})", inferred.name, inferred.source);
+    println!(
+        "{} Inferred project name: {} (from {})",
+        "✓".green(),
+        inferred.name.cyan(),
+        inferred.source
+    );
+
+    if !cli.yes && std::io::stdin().is_terminal() && !confirm("Use this name?")? {
+        return Err(eyre::eyre!("Aborted, pass the project name explicitly"));
+    }
+
+    Ok(inferred.name)
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [Y/n] ", question);
+    std::io::stdout().flush().context("Failed to flush stdout")?;
+
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "" | "y" | "yes"))
+}
+
+fn create_project(cli: &Cli, config: &Config) -> Result<()> {
+    let target_dir = &target_dir(cli);
+    let project = &project_name(cli, target_dir)?;
+
+    crate_name::validate(project)?;
+    for warning in crate_name::warnings(project) {
+        warn!("{}", warning);
+        println!("{} {}", "⚠".yellow(), warning);
     }
 
     info!("Creating project: {}", project);
     println!("{} Creating project: {}", "✓".green(), project.cyan());
 
+    let author = author::resolve(cli.author.as_deref(), config);
+    info!("Using author {} (from {})", author.value, author.source);
+    if author.source == author::AuthorSource::Placeholder {
+        warn!("No author configured or detected, using placeholder");
+        println!(
+            "{} No author configured or detected, using placeholder (set --author or default-author)",
+            "⚠".yellow()
+        );
+    }
+
+    let workspace = workspace::Workspace::find_enclosing(target_dir)?;
+    if let Some(workspace) = &workspace {
+        if config.template.kind == ProjectKind::Workspace {
+            return Err(eyre::eyre!(
+                "Cannot create a workspace inside the workspace at {} (use --kind package to add a member)",
+                workspace.root.display()
+            ));
+        }
+        info!("Target is inside the workspace at {}", workspace.root.display());
+        println!(
+            "{} Found enclosing workspace: {}",
+            "✓".green(),
+            workspace.root.display()
+        );
+    }
+
+    config.template.check_components()?;
+
+    // Prompts so far exit on Ctrl-C; from here on it rolls back what was written
+    transaction::begin_writes();
+
     if target_dir.exists() {
-        if !is_scaffoldable_directory(target_dir)? {
+        let blocking = blocking_entries(target_dir, &config.allowed_files)?;
+        if !blocking.is_empty() {
+            let listed = blocking.join(", ");
             if cli.force {
-                warn!("Directory contains non-repo files, proceeding due to --force");
+                warn!(
+                    "Directory contains non-repo files ({}), proceeding due to --force",
+                    listed
+                );
                 println!(
-                    "{} Directory contains non-repo files, proceeding due to --force",
-                    "⚠".yellow()
+                    "{} Directory contains non-repo files ({}), proceeding due to --force",
+                    "⚠".yellow(),
+                    listed
                 );
             } else {
                 return Err(eyre::eyre!(
-                    "Directory {} already exists and contains non-repo files (use --force to override)",
-                    target_dir.display()
+                    "Directory {} already exists and contains non-repo files: {} (use --force to override, or add them to allowed-files in scaffold.yml)",
+                    target_dir.display(),
+                    listed
                 ));
             }
         }
         println!("{} Using existing directory: {}", "✓".green(), target_dir.display());
+
+        scaffold_journaled(project, target_dir, &author.value, cli, config, workspace.as_ref())?;
+    } else if workspace.is_some() {
+        // No staging here: cargo rejects a package under a workspace root that isn't one of its members
+        scaffold_journaled(project, target_dir, &author.value, cli, config, workspace.as_ref())?;
+        println!("{} Created directory: {}", "✓".green(), target_dir.display());
     } else {
-        fs::create_dir_all(target_dir).context("Failed to create project directory")?;
+        // Generate into a staging directory and move it into place only once everything succeeded
+        let staging = transaction::StagingDir::new(target_dir)?;
+        let mut writer = templates::FileWriter::new(staging.path(), conflict_resolver(cli, config)?);
+        scaffold_into(project, staging.path(), &author.value, cli, config, None, &mut writer)?;
+        staging.commit()?;
         println!("{} Created directory: {}", "✓".green(), target_dir.display());
     }
 
-    templates::generate_project(
-        project,
-        target_dir,
-        cli.author.as_ref().unwrap_or(&config.default_author),
-        config,
-        cli.no_deps,
-        cli.force,
-    )?;
+    println!("\n{} Project {} created successfully!", "🎉".green(), project.cyan());
+    println!("Next steps:");
+    if target_dir != Path::new(".") {
+        println!("  cd {}", target_dir.display());
+    }
+    println!("  cargo run");
 
-    if !cli.no_git && config.create_git_repo {
+    Ok(())
+}
+
+/// Path rules from config win, then --on-conflict, then on-conflict from config;
+/// without either, --force keeps existing files and a clean repo directory gets them overwritten
+fn conflict_resolver(cli: &Cli, config: &Config) -> Result<conflict::ConflictResolver> {
+    let default = cli.on_conflict.or(config.on_conflict).unwrap_or(if cli.force {
+        conflict::ConflictPolicy::Skip
+    } else {
+        conflict::ConflictPolicy::Overwrite
+    });
+
+    conflict::ConflictResolver::new(default, &config.conflict_rules)
+}
+
+/// Scaffold in place, journaling every change so a failure restores the original state
+fn scaffold_journaled(
+    project: &str,
+    target_dir: &Path,
+    author: &str,
+    cli: &Cli,
+    config: &Config,
+    workspace: Option<&workspace::Workspace>,
+) -> Result<()> {
+    let mut writer = templates::FileWriter::new(target_dir, conflict_resolver(cli, config)?);
+    if let Err(e) = scaffold_into(project, target_dir, author, cli, config, workspace, &mut writer) {
+        error!("Scaffold failed, rolling back: {}", e);
+        match writer.journal.rollback() {
+            Ok(count) => println!(
+                "{} Rolled back {} change(s) to {}",
+                "↩".yellow(),
+                count,
+                target_dir.display()
+            ),
+            Err(rollback_err) => error!("{}", rollback_err),
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Generate files, init git, add dependencies and verify the build, checking for Ctrl-C between steps
+fn scaffold_into(
+    project: &str,
+    target_dir: &Path,
+    author: &str,
+    cli: &Cli,
+    config: &Config,
+    workspace: Option<&workspace::Workspace>,
+    writer: &mut templates::FileWriter,
+) -> Result<()> {
+    templates::generate_project(project, target_dir, author, config, cli.no_deps, workspace, writer)?;
+    transaction::check_interrupted()?;
+
+    // Inside a workspace, the lock file and build output live at its root
+    let build_root = workspace.map_or(target_dir, |workspace| workspace.root.as_path());
+
+    if let Some(workspace) = workspace {
+        writer.journal.record(&workspace.manifest())?;
+        if workspace.add_member(target_dir)? {
+            println!(
+                "{} Added to workspace members in {}",
+                "✓".green(),
+                workspace.manifest().display()
+            );
+        }
+        transaction::check_interrupted()?;
+    }
+
+    if workspace.is_some() {
+        info!("Skipping git init, the workspace owns the repository");
+    } else if !cli.no_git && config.create_git_repo {
+        writer.journal.record(&target_dir.join(".git"))?;
         init_git_repo(target_dir)?;
+        transaction::check_interrupted()?;
     }
 
     if !cli.no_deps {
-        add_dependencies(target_dir, config)?;
+        writer.journal.record(&target_dir.join("Cargo.toml"))?;
+        writer.journal.record(&build_root.join("Cargo.lock"))?;
+        match config.template.kind {
+            ProjectKind::Package => add_dependencies(target_dir, config)?,
+            ProjectKind::Workspace => pin_workspace_dependencies(target_dir)?,
+        }
+        transaction::check_interrupted()?;
     }
 
     if !cli.no_verify {
+        writer.journal.record(&build_root.join("Cargo.lock"))?;
+        writer.journal.record(&build_root.join("target"))?;
         verify_build(target_dir)?;
+        transaction::check_interrupted()?;
     }
 
-    println!("\n{} Project {} created successfully!", "🎉".green(), project.cyan());
-    println!("Next steps:");
-    println!("  cd {}", target_dir.display());
-    println!("  cargo run");
-
     Ok(())
 }
 
-fn init_git_repo(target_dir: &PathBuf) -> Result<()> {
+fn init_git_repo(target_dir: &Path) -> Result<()> {
     // Skip if .git already exists (e.g., cloned repo)
     if target_dir.join(".git").exists() {
         info!("Git repository already exists, skipping init");
@@ -184,13 +383,22 @@ fn init_git_repo(target_dir: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn add_dependencies(target_dir: &PathBuf, config: &Config) -> Result<()> {
+fn add_dependencies(target_dir: &Path, config: &Config) -> Result<()> {
     info!("Adding dependencies");
     println!("{} Adding dependencies...", "✓".green());
 
-    for dep in &config.template.dependencies {
+    let dependencies = config.template.resolved_dependencies();
+    let dev_dependencies = config.template.resolved_dev_dependencies();
+    let all = dependencies
+        .iter()
+        .map(|dep| (dep, false))
+        .chain(dev_dependencies.iter().map(|dep| (dep, true)));
+    for (dep, dev) in all {
         let mut cmd = Command::new("cargo");
         cmd.args(["add", &dep.name]).current_dir(target_dir);
+        if dev {
+            cmd.arg("--dev");
+        }
 
         if !dep.features.is_empty() {
             let features = format!("--features={}", dep.features.join(","));
@@ -211,7 +419,29 @@ fn add_dependencies(target_dir: &PathBuf, config: &Config) -> Result<()> {
     Ok(())
 }
 
-fn verify_build(target_dir: &PathBuf) -> Result<()> {
+/// Resolve the wildcard `[workspace.dependencies]` and pin them to the versions cargo picked
+fn pin_workspace_dependencies(target_dir: &Path) -> Result<()> {
+    info!("Resolving workspace dependencies");
+    println!("{} Resolving workspace dependencies...", "✓".green());
+
+    let output = Command::new("cargo")
+        .args(["generate-lockfile"])
+        .current_dir(target_dir)
+        .output()
+        .context("Failed to run cargo generate-lockfile")?;
+
+    if !output.status.success() {
+        error!("Failed to resolve workspace dependencies");
+        println!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(eyre::eyre!("Failed to resolve workspace dependencies"));
+    }
+
+    let pinned = workspace::pin_dependencies(target_dir)?;
+    println!("{} Pinned {} workspace dependencies", "✓".green(), pinned);
+    Ok(())
+}
+
+fn verify_build(target_dir: &Path) -> Result<()> {
     info!("Verifying project builds");
     println!("{} Verifying project builds...", "✓".green());
 
@@ -232,14 +462,105 @@ fn verify_build(target_dir: &PathBuf) -> Result<()> {
     Ok(())
 }
 
+fn load_config(cli: &Cli) -> Result<Config> {
+    let mut config = Config::load(cli.config.as_ref(), Some(&target_dir(cli)), !cli.no_command_expansion)
+        .and_then(|config| config.with_profile(cli.profile.as_deref()))
+        .context("Failed to load configuration")?;
+
+    if let Some(kind) = cli.kind {
+        config.template.kind = kind;
+    }
+    if cli.lib {
+        config.template.create_lib = true;
+    }
+    if cli.completions {
+        config.template.create_completions = true;
+    }
+    if let Some(spec) = &cli.cli_spec {
+        config.template.cli.spec = Some(spec.clone());
+    }
+    if let Some(logging) = cli.logging {
+        config.template.logging = logging;
+    }
+    if let Some(format) = cli.config_format {
+        config.template.config_format = format;
+    }
+    for component in &cli.components {
+        if !config.template.has_component(*component) {
+            config.template.components.push(*component);
+        }
+    }
+
+    // Arguments that read environment variables need clap's env feature
+    if let Some(spec) = &config.template.cli.spec
+        && cli_spec::CliSpec::load(spec)?.uses_env()
+        && let Some(clap) = config.template.dependencies.iter_mut().find(|dep| dep.name == "clap")
+        && !clap.features.iter().any(|feature| feature == "env")
+    {
+        clap.features.push("env".to_string());
+    }
+
+    Ok(config)
+}
+
+fn show_config(cli: &Cli) -> Result<()> {
+    let config = load_config(cli)?;
+    let author = author::resolve(None, &config);
+
+    if config.sources.is_empty() {
+        println!("# sources: none (using defaults)");
+    }
+    for source in &config.sources {
+        println!("# source: {}", source.display());
+    }
+    if let Some(profile) = &config.active_profile {
+        println!("# profile: {}", profile);
+    }
+    println!("# author: {} (from {})", author.value, author.source);
+    print!(
+        "{}",
+        serde_yaml::to_string(&config).context("Failed to serialize config")?
+    );
+
+    Ok(())
+}
+
+fn run_config_command(action: &ConfigAction, cli: &Cli) -> Result<()> {
+    match action {
+        ConfigAction::Schema => {
+            println!("{}", Config::json_schema()?);
+        }
+        ConfigAction::Show => show_config(cli)?,
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
+    completions::handle_completion_request();
     setup_logging().context("Failed to setup logging")?;
+    transaction::install_interrupt_handler()?;
 
     let cli = Cli::parse();
 
-    let config = Config::load(cli.config.as_ref()).context("Failed to load configuration")?;
+    match &cli.command {
+        Some(CliCommand::Config { action }) => return run_config_command(action, &cli),
+        Some(CliCommand::RegenCli { spec, directory }) => {
+            let spec = spec.clone().unwrap_or_else(|| directory.join(cli_spec::SPEC_FILE));
+            return templates::regenerate_cli(&spec, directory);
+        }
+        Some(CliCommand::Completions { shell }) => return completions::print_registration(*shell),
+        Some(CliCommand::Man { dir }) => return completions::man(dir.as_deref()),
+        None => {}
+    }
 
-    info!("Starting scaffold with project name: {}", cli.project);
+    let config = load_config(&cli)?;
+
+    if let Some(profile) = &config.active_profile {
+        println!("{} Using profile: {}", "✓".green(), profile.cyan());
+    }
+
+    info!("Starting scaffold with project name: {:?}", cli.project);
 
     create_project(&cli, &config).context("Failed to create project")?;
 
@@ -255,11 +576,23 @@ mod tests {
 
     fn create_test_cli(project: &str) -> Cli {
         Cli {
-            project: project.to_string(),
+            command: None,
+            project: Some(project.to_string()),
             author: Some("Test Author <test@example.com>".to_string()),
             directory: None,
             config: None,
+            profile: None,
+            no_command_expansion: false,
+            kind: None,
+            lib: false,
+            completions: false,
+            cli_spec: None,
+            logging: None,
+            config_format: None,
+            components: vec![],
+            yes: false,
             force: false,
+            on_conflict: None,
             no_git: true,
             no_sample_config: false,
             no_verify: true,
@@ -271,14 +604,36 @@ mod tests {
         Config::default()
     }
 
+    /// Config whose dependency `cargo add` rejects without touching the network
+    fn create_failing_deps_config() -> Config {
+        let mut config = Config::default();
+        config.template.dependencies = vec![config::Dependency {
+            name: "not a crate!".to_string(),
+            features: vec![],
+        }];
+        config
+    }
+
     #[test]
     fn test_create_project_validates_empty_name() {
         let cli = Cli {
-            project: "".to_string(),
+            command: None,
+            project: Some("".to_string()),
             author: None,
             directory: None,
             config: None,
+            profile: None,
+            no_command_expansion: false,
+            kind: None,
+            lib: false,
+            completions: false,
+            cli_spec: None,
+            logging: None,
+            config_format: None,
+            components: vec![],
+            yes: false,
             force: false,
+            on_conflict: None,
             no_git: true,
             no_sample_config: false,
             no_verify: true,
@@ -294,11 +649,23 @@ mod tests {
     #[test]
     fn test_create_project_validates_name_starting_with_dash() {
         let cli = Cli {
-            project: "-invalid".to_string(),
+            command: None,
+            project: Some("-invalid".to_string()),
             author: None,
             directory: None,
             config: None,
+            profile: None,
+            no_command_expansion: false,
+            kind: None,
+            lib: false,
+            completions: false,
+            cli_spec: None,
+            logging: None,
+            config_format: None,
+            components: vec![],
+            yes: false,
             force: false,
+            on_conflict: None,
             no_git: true,
             no_sample_config: false,
             no_verify: true,
@@ -314,11 +681,23 @@ mod tests {
     #[test]
     fn test_create_project_validates_name_starting_with_underscore() {
         let cli = Cli {
-            project: "_invalid".to_string(),
+            command: None,
+            project: Some("_invalid".to_string()),
             author: None,
             directory: None,
             config: None,
+            profile: None,
+            no_command_expansion: false,
+            kind: None,
+            lib: false,
+            completions: false,
+            cli_spec: None,
+            logging: None,
+            config_format: None,
+            components: vec![],
+            yes: false,
             force: false,
+            on_conflict: None,
             no_git: true,
             no_sample_config: false,
             no_verify: true,
@@ -334,11 +713,23 @@ mod tests {
     #[test]
     fn test_create_project_validates_invalid_characters() {
         let cli = Cli {
-            project: "invalid@name".to_string(),
+            command: None,
+            project: Some("invalid@name".to_string()),
             author: None,
             directory: None,
             config: None,
+            profile: None,
+            no_command_expansion: false,
+            kind: None,
+            lib: false,
+            completions: false,
+            cli_spec: None,
+            logging: None,
+            config_format: None,
+            components: vec![],
+            yes: false,
             force: false,
+            on_conflict: None,
             no_git: true,
             no_sample_config: false,
             no_verify: true,
@@ -389,6 +780,46 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("contains non-repo files"));
     }
 
+    #[test]
+    fn test_create_project_lists_every_blocking_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("test-project");
+        fs::create_dir_all(project_dir.join("docs")).unwrap();
+        fs::write(project_dir.join("flake.nix"), "{}").unwrap();
+        fs::write(project_dir.join(".envrc"), "use flake").unwrap();
+        fs::write(project_dir.join("README.md"), "# Test").unwrap();
+
+        let mut cli = create_test_cli("test-project");
+        cli.directory = Some(project_dir);
+
+        let err = create_project(&cli, &create_test_config()).unwrap_err().to_string();
+        assert!(err.contains("non-repo files: .envrc, docs, flake.nix"), "{}", err);
+    }
+
+    #[test]
+    fn test_create_project_accepts_allowed_files_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("test-project");
+        fs::create_dir_all(project_dir.join("docs")).unwrap();
+        fs::write(project_dir.join("flake.nix"), "{}").unwrap();
+        fs::write(project_dir.join(".envrc"), "use flake").unwrap();
+
+        let mut cli = create_test_cli("test-project");
+        cli.directory = Some(project_dir.clone());
+        let mut config = create_test_config();
+        config.allowed_files = vec![".envrc".to_string(), "*.NIX".to_string(), "docs/".to_string()];
+
+        let result = create_project(&cli, &config);
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(project_dir.join("Cargo.toml").exists());
+    }
+
+    #[test]
+    fn test_blocking_entries_rejects_invalid_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(blocking_entries(temp_dir.path(), &["[oops".to_string()]).is_err());
+    }
+
     #[test]
     fn test_create_project_succeeds_on_directory_with_repo_files_only() {
         let temp_dir = TempDir::new().unwrap();
@@ -412,6 +843,45 @@ mod tests {
         assert!(project_dir.join(".gitignore").exists());
     }
 
+    #[test]
+    fn test_conflict_resolver_precedence() {
+        let mut cli = create_test_cli("test-project");
+        let mut config = create_test_config();
+        let readme = Path::new("README.md");
+
+        assert_eq!(
+            conflict_resolver(&cli, &config).unwrap().policy_for(readme),
+            conflict::ConflictPolicy::Overwrite
+        );
+
+        cli.force = true;
+        assert_eq!(
+            conflict_resolver(&cli, &config).unwrap().policy_for(readme),
+            conflict::ConflictPolicy::Skip
+        );
+
+        config.on_conflict = Some(conflict::ConflictPolicy::Backup);
+        assert_eq!(
+            conflict_resolver(&cli, &config).unwrap().policy_for(readme),
+            conflict::ConflictPolicy::Backup
+        );
+
+        cli.on_conflict = Some(conflict::ConflictPolicy::Merge);
+        assert_eq!(
+            conflict_resolver(&cli, &config).unwrap().policy_for(readme),
+            conflict::ConflictPolicy::Merge
+        );
+
+        config.conflict_rules = vec![conflict::ConflictRule {
+            path: "README.md".to_string(),
+            policy: conflict::ConflictPolicy::Skip,
+        }];
+        assert_eq!(
+            conflict_resolver(&cli, &config).unwrap().policy_for(readme),
+            conflict::ConflictPolicy::Skip
+        );
+    }
+
     #[test]
     fn test_create_project_succeeds_on_directory_with_git_folder() {
         let temp_dir = TempDir::new().unwrap();
@@ -447,6 +917,207 @@ mod tests {
         assert!(project_dir.join("src").exists());
     }
 
+    #[test]
+    fn test_create_project_rolls_back_existing_directory_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("test-rollback");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(project_dir.join("README.md"), "# Original").unwrap();
+
+        let mut cli = create_test_cli("test-rollback");
+        cli.directory = Some(project_dir.clone());
+        cli.no_deps = false;
+        let config = create_failing_deps_config();
+
+        let result = create_project(&cli, &config);
+        assert!(result.is_err());
+
+        let remaining: Vec<_> = fs::read_dir(&project_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(remaining, vec!["README.md"]);
+        assert_eq!(fs::read_to_string(project_dir.join("README.md")).unwrap(), "# Original");
+    }
+
+    #[test]
+    fn test_create_project_removes_new_directory_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("nested").join("test-staged");
+
+        let mut cli = create_test_cli("test-staged");
+        cli.directory = Some(project_dir.clone());
+        cli.no_deps = false;
+        let config = create_failing_deps_config();
+
+        let result = create_project(&cli, &config);
+        assert!(result.is_err());
+
+        assert!(!project_dir.exists());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_create_project_infers_name_without_positional() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("Inferred_Tool");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        for project in [None, Some(".".to_string())] {
+            let mut cli = create_test_cli("unused");
+            cli.project = project;
+            cli.directory = Some(project_dir.clone());
+            cli.force = true;
+
+            let result = create_project(&cli, &create_test_config());
+            assert!(result.is_ok(), "{:?}", result);
+
+            let cargo_toml = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+            assert!(cargo_toml.contains("name = \"inferred-tool\""));
+        }
+    }
+
+    #[test]
+    fn test_project_path_names_project_after_last_component() {
+        let cli = create_test_cli("./man");
+        assert_eq!(explicit_project(&cli), Some("man"));
+        assert_eq!(target_dir(&cli), PathBuf::from("./man"));
+    }
+
+    #[test]
+    fn test_create_project_named_like_subcommand_after_double_dash() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("man");
+        let dir = project_dir.to_string_lossy().into_owned();
+        let cli = Cli::try_parse_from([
+            "scaffold",
+            "-a",
+            "Test Author <test@example.com>",
+            "-d",
+            &dir,
+            "--no-git",
+            "--no-deps",
+            "--no-verify",
+            "--",
+            "man",
+        ])
+        .unwrap();
+        assert!(cli.command.is_none());
+
+        let result = create_project(&cli, &create_test_config());
+        assert!(result.is_ok(), "{:?}", result);
+
+        let cargo_toml = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"man\""));
+    }
+
+    #[test]
+    fn test_target_dir_defaults() {
+        let mut cli = create_test_cli("named");
+        assert_eq!(target_dir(&cli), PathBuf::from("named"));
+
+        cli.project = Some(".".to_string());
+        assert_eq!(target_dir(&cli), PathBuf::from("."));
+
+        cli.project = None;
+        assert_eq!(target_dir(&cli), PathBuf::from("."));
+
+        cli.directory = Some(PathBuf::from("/tmp/elsewhere"));
+        assert_eq!(target_dir(&cli), PathBuf::from("/tmp/elsewhere"));
+    }
+
+    #[test]
+    fn test_create_project_joins_enclosing_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = []\n\n[workspace.dependencies]\nclap = \"4\"\n",
+        )
+        .unwrap();
+        let project_dir = temp_dir.path().join("crates").join("member-tool");
+
+        let mut cli = create_test_cli("member-tool");
+        cli.directory = Some(project_dir.clone());
+
+        let result = create_project(&cli, &create_test_config());
+        assert!(result.is_ok(), "{:?}", result);
+
+        let root_manifest = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+        assert!(root_manifest.contains("members = [\"crates/member-tool\"]"));
+
+        let cargo_toml = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("clap = { workspace = true, features = [\"derive\"] }"));
+        assert!(cargo_toml.contains("eyre = \"*\""));
+
+        assert!(!project_dir.join(".otto.yml").exists());
+        assert!(!project_dir.join(".github").exists());
+        assert!(!project_dir.join(".pre-commit-config.yaml").exists());
+        assert!(!project_dir.join(".git").exists());
+    }
+
+    #[test]
+    fn test_create_project_workspace_kind() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("multi-tool");
+        let mut config = create_test_config();
+        config.template.kind = ProjectKind::Workspace;
+
+        let mut cli = create_test_cli("multi-tool");
+        cli.directory = Some(project_dir.clone());
+
+        let result = create_project(&cli, &config);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let root_manifest = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(root_manifest.contains("[workspace.dependencies]"));
+        assert!(project_dir.join("crates/multi-tool-cli/src/main.rs").exists());
+        assert!(project_dir.join("crates/multi-tool-core/src/lib.rs").exists());
+        assert!(project_dir.join(".otto.yml").exists());
+    }
+
+    #[test]
+    fn test_create_project_rejects_workspace_kind_inside_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        let project_dir = temp_dir.path().join("nested");
+        let mut config = create_test_config();
+        config.template.kind = ProjectKind::Workspace;
+
+        let mut cli = create_test_cli("nested");
+        cli.directory = Some(project_dir.clone());
+
+        let result = create_project(&cli, &config);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Cannot create a workspace inside")
+        );
+        assert!(!project_dir.exists());
+    }
+
+    #[test]
+    fn test_create_project_rolls_back_workspace_membership_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = "[workspace]\nmembers = []\n";
+        fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+        let project_dir = temp_dir.path().join("member-tool");
+
+        let mut cli = create_test_cli("member-tool");
+        cli.directory = Some(project_dir.clone());
+        cli.no_deps = false;
+
+        let result = create_project(&cli, &create_failing_deps_config());
+        assert!(result.is_err());
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap(),
+            manifest
+        );
+        assert!(!project_dir.exists());
+    }
+
     #[test]
     fn test_create_project_uses_default_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -489,7 +1160,10 @@ mod tests {
         let mut cli = create_test_cli("test-config-author");
         cli.directory = Some(project_dir.clone());
         cli.author = None; // No author specified
-        let config = create_test_config();
+        let config = Config {
+            default_author: "Config Author <config@test.com>".to_string(),
+            ..create_test_config()
+        };
 
         let result = create_project(&cli, &config);
         assert!(result.is_ok());
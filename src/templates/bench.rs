@@ -0,0 +1,82 @@
+use super::FileWriter;
+use super::source::format_parser;
+use crate::config::ConfigFormat;
+use eyre::Result;
+use std::path::Path;
+
+/// benches/<project>.rs, a criterion group to grow the project's own benchmarks from
+///
+/// It starts with parsing a config file into the Config exported by `lib`, the package name of the
+/// project's lib target.
+pub(super) fn generate_bench(
+    project_name: &str,
+    format: ConfigFormat,
+    lib: &str,
+    package_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let (_, parser, _) = format_parser(format);
+    // Config file fixture as a Rust string literal
+    let fixture = match format {
+        ConfigFormat::Yaml => r#""name: Bench User\nage: 30\ndebug: false\n""#,
+        ConfigFormat::Toml => r#""name = \"Bench User\"\nage = 30\ndebug = false\n""#,
+        ConfigFormat::Json => r##"r#"{"name": "Bench User", "age": 30, "debug": false}"#"##,
+    };
+
+    let benches_dir = package_dir.join("benches");
+    writer.create_dir_all(&benches_dir)?;
+
+    let bench_rs = r#"//! Benchmarks for {{PROJECT}}; run with `cargo bench`, reports land in target/criterion
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+use {{CRATE}}::Config;
+
+/// A config file like the ones {{PROJECT}} loads
+const CONFIG: &str = {{FIXTURE}};
+
+fn bench_parse_config(c: &mut Criterion) {
+    c.bench_function("parse config", |b| {
+        b.iter(|| {{PARSER}}::from_str::<Config>(black_box(CONFIG)))
+    });
+}
+
+criterion_group!(benches, bench_parse_config);
+criterion_main!(benches);
+"#
+    .replace("{{CRATE}}", &lib.replace('-', "_"))
+    .replace("{{FIXTURE}}", fixture)
+    .replace("{{PARSER}}", parser)
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&benches_dir.join(format!("{}.rs", project_name)), &bench_rs)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_bench() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_bench(
+            "tool",
+            ConfigFormat::Toml,
+            "tool-cli",
+            temp_dir.path(),
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let bench_rs = fs::read_to_string(temp_dir.path().join("benches/tool.rs")).unwrap();
+        assert!(bench_rs.contains("use tool_cli::Config;"));
+        assert!(bench_rs.contains("toml::from_str::<Config>(black_box(CONFIG))"));
+        assert!(bench_rs.contains("const CONFIG: &str = \"name = \\\"Bench User\\\"\\n"));
+        assert!(bench_rs.contains("criterion_group!(benches, bench_parse_config);"));
+        assert!(!bench_rs.contains("{{"));
+    }
+}
@@ -0,0 +1,429 @@
+use super::FileWriter;
+use crate::config::{Component, Config, Dependency};
+use crate::workspace::Workspace;
+use eyre::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+pub(super) fn generate_cargo_toml(
+    project_name: &str,
+    target_dir: &Path,
+    author: &str,
+    config: &Config,
+    include_deps: bool,
+    workspace: Option<&Workspace>,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let mut cargo_toml = format!(
+        r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2024"
+authors = ["{}"]
+build = "build.rs"
+description = "A CLI application generated by rust-scaffold"
+
+[dependencies]"#,
+        project_name, author
+    );
+
+    if include_deps {
+        // Add dependencies directly to Cargo.toml, inheriting versions the workspace declares
+        push_dependencies(&mut cargo_toml, &config.template.resolved_dependencies(), workspace);
+    } else {
+        cargo_toml.push_str("\n# Dependencies will be added via cargo add");
+    }
+
+    cargo_toml.push_str("\n\n[dev-dependencies]");
+    if include_deps {
+        push_dependencies(&mut cargo_toml, &config.template.resolved_dev_dependencies(), workspace);
+    } else {
+        cargo_toml.push_str("\n# Dev-dependencies will be added via cargo add --dev");
+    }
+
+    cargo_toml.push_str("\n\n[build-dependencies]\n# For build.rs if needed\n");
+    cargo_toml.push_str(&bench_target(project_name, config));
+
+    writer.write_file(&target_dir.join("Cargo.toml"), &cargo_toml)?;
+
+    Ok(())
+}
+
+/// `[[bench]]` table for benches/<project>.rs when the bench component is selected; criterion brings its own harness
+fn bench_target(project_name: &str, config: &Config) -> String {
+    if config.template.has_component(Component::Bench) {
+        format!("\n[[bench]]\nname = \"{}\"\nharness = false\n", project_name)
+    } else {
+        String::new()
+    }
+}
+
+/// Append a manifest line per dependency, inheriting the versions an enclosing workspace declares
+fn push_dependencies(manifest: &mut String, dependencies: &[Dependency], workspace: Option<&Workspace>) {
+    for dep in dependencies {
+        let inherit = workspace.is_some_and(|workspace| workspace.declares(&dep.name));
+        let source = if inherit { "workspace = true" } else { "version = \"*\"" };
+        manifest.push_str(&dependency_entry(dep, source));
+    }
+}
+
+/// Manifest line for `dep`, e.g. `clap = { version = "*", features = ["derive"] }`
+///
+/// `source` is either `version = "*"` or `workspace = true`; plain wildcard versions use the short form.
+fn dependency_entry(dep: &Dependency, source: &str) -> String {
+    if dep.features.is_empty() && source == "version = \"*\"" {
+        format!("\n{} = \"*\"", dep.name)
+    } else if dep.features.is_empty() {
+        format!("\n{} = {{ {} }}", dep.name, source)
+    } else {
+        format!(
+            "\n{} = {{ {}, features = [{}] }}",
+            dep.name,
+            source,
+            dep.features
+                .iter()
+                .map(|f| format!("\"{}\"", f))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Root virtual manifest of a workspace-kind project
+///
+/// Versions are wildcards here; they are pinned from Cargo.lock once cargo has resolved them.
+pub(super) fn generate_workspace_manifest(
+    project_name: &str,
+    target_dir: &Path,
+    author: &str,
+    config: &Config,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let mut cargo_toml = format!(
+        r#"[workspace]
+resolver = "3"
+members = ["crates/*"]
+
+[workspace.package]
+version = "0.1.0"
+edition = "2024"
+authors = ["{}"]
+license = "{}"
+description = "A CLI application generated by rust-scaffold"
+
+[workspace.dependencies]
+{}-core = {{ path = "crates/{}-core" }}"#,
+        author, config.default_license, project_name, project_name
+    );
+
+    let dependencies = config.template.resolved_dependencies();
+    let dev_dependencies = config.template.resolved_dev_dependencies();
+    let mut declared = HashSet::new();
+    for dep in dependencies.iter().chain(&dev_dependencies) {
+        // A crate that is both a dependency and a dev-dependency is declared once
+        if declared.insert(dep.name.as_str()) {
+            cargo_toml.push_str(&dependency_entry(dep, "version = \"*\""));
+        }
+    }
+    cargo_toml.push('\n');
+
+    writer.write_file(&target_dir.join("Cargo.toml"), &cargo_toml)?;
+
+    Ok(())
+}
+
+/// Manifests of the `<name>-cli` binary and `<name>-core` library crates, inheriting from the workspace
+pub(super) fn generate_member_manifests(
+    project_name: &str,
+    cli_dir: &Path,
+    core_dir: &Path,
+    config: &Config,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let mut cli_toml = format!(
+        r#"[package]
+name = "{}-cli"
+version.workspace = true
+edition.workspace = true
+authors.workspace = true
+license.workspace = true
+description.workspace = true
+build = "build.rs"
+
+[[bin]]
+name = "{}"
+path = "src/main.rs"
+
+[dependencies]
+{}-core.workspace = true"#,
+        project_name, project_name, project_name
+    );
+
+    for dep in &config.template.resolved_dependencies() {
+        cli_toml.push_str(&format!("\n{}.workspace = true", dep.name));
+    }
+    cli_toml.push_str("\n\n[dev-dependencies]");
+    for dep in &config.template.resolved_dev_dependencies() {
+        cli_toml.push_str(&format!("\n{}.workspace = true", dep.name));
+    }
+    cli_toml.push('\n');
+    cli_toml.push_str(&bench_target(project_name, config));
+
+    writer.write_file(&cli_dir.join("Cargo.toml"), &cli_toml)?;
+
+    let core_toml = format!(
+        r#"[package]
+name = "{}-core"
+version.workspace = true
+edition.workspace = true
+authors.workspace = true
+license.workspace = true
+description.workspace = true
+
+[dependencies]
+"#,
+        project_name
+    );
+
+    writer.write_file(&core_dir.join("Cargo.toml"), &core_toml)?;
+
+    Ok(())
+}
+
+/// build.rs wiring `git describe` into GIT_DESCRIBE; `git_dir` is the repository's .git relative to the crate
+pub(super) fn generate_build_rs(target_dir: &Path, git_dir: &str, writer: &mut FileWriter) -> Result<()> {
+    let build_rs = r#"// Simple pattern for git describe -> version
+use std::process::Command;
+
+fn main() {
+    let git_describe = Command::new("git")
+        .args(["describe", "--tags", "--always"])
+        .output()
+        .and_then(|output| {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            } else {
+                Err(std::io::Error::other("git describe failed"))
+            }
+        })
+        .unwrap_or_else(|_| {
+            // Fallback to Cargo.toml version when git describe fails
+            env!("CARGO_PKG_VERSION").to_string()
+        });
+
+    println!("cargo:rustc-env=GIT_DESCRIBE={}", git_describe);
+    println!("cargo:rerun-if-changed={{GIT_DIR}}/HEAD");
+    println!("cargo:rerun-if-changed={{GIT_DIR}}/refs/");
+}
+"#
+    .replace("{{GIT_DIR}}", git_dir);
+
+    writer.write_file(&target_dir.join("build.rs"), &build_rs)?;
+
+    Ok(())
+}
+
+pub(super) fn generate_clippy_toml(target_dir: &Path, writer: &mut FileWriter) -> Result<()> {
+    let clippy_toml = r#"too-many-arguments-threshold = 12
+"#;
+
+    writer.write_file(&target_dir.join("clippy.toml"), clippy_toml)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_config() -> Config {
+        Config::default()
+    }
+
+    #[test]
+    fn test_generate_cargo_toml_basic_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-cargo";
+        let author = "Test Author <test@example.com>";
+        let config = create_test_config();
+
+        let result = generate_cargo_toml(
+            project_name,
+            temp_dir.path(),
+            author,
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+
+        assert!(cargo_toml.contains(&format!("name = \"{}\"", project_name)));
+        assert!(cargo_toml.contains("version = \"0.1.0\""));
+        assert!(cargo_toml.contains("edition = \"2024\""));
+        assert!(cargo_toml.contains(author));
+        assert!(cargo_toml.contains("build = \"build.rs\""));
+        assert!(cargo_toml.contains("[dependencies]"));
+        assert!(cargo_toml.contains("[build-dependencies]"));
+    }
+
+    #[test]
+    fn test_generate_cargo_toml_with_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-deps";
+        let author = "Test Author";
+        let config = create_test_config();
+
+        let result = generate_cargo_toml(
+            project_name,
+            temp_dir.path(),
+            author,
+            &config,
+            true,
+            None,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+
+        // Check that dependencies are included
+        assert!(cargo_toml.contains("clap"));
+        assert!(cargo_toml.contains("eyre"));
+        assert!(cargo_toml.contains("serde"));
+        assert!(cargo_toml.contains("derive"));
+        assert!(cargo_toml.contains("[dev-dependencies]\ntempfile = \"*\"\nassert_cmd = \"*\"\npredicates = \"*\""));
+        assert!(!cargo_toml.contains("[[bench]]"));
+    }
+
+    #[test]
+    fn test_generate_cargo_toml_with_bench_component() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.components.push(Component::Bench);
+
+        generate_cargo_toml(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            true,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("predicates = \"*\"\ncriterion = \"*\""));
+        assert!(cargo_toml.ends_with("[[bench]]\nname = \"tool\"\nharness = false\n"));
+        assert!(cargo_toml.parse::<toml_edit::DocumentMut>().is_ok());
+    }
+
+    #[test]
+    fn test_generate_cargo_toml_without_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-no-deps";
+        let author = "Test Author";
+        let config = create_test_config();
+
+        let result = generate_cargo_toml(
+            project_name,
+            temp_dir.path(),
+            author,
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+
+        // Check that dependencies comment is included instead
+        assert!(cargo_toml.contains("Dependencies will be added via cargo add"));
+        assert!(cargo_toml.contains("Dev-dependencies will be added via cargo add --dev"));
+        assert!(!cargo_toml.contains("clap = "));
+        assert!(!cargo_toml.contains("eyre = "));
+    }
+
+    #[test]
+    fn test_generate_build_rs() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = generate_build_rs(temp_dir.path(), ".git", &mut FileWriter::default());
+        assert!(result.is_ok());
+
+        let build_rs = fs::read_to_string(temp_dir.path().join("build.rs")).unwrap();
+
+        assert!(build_rs.contains("fn main()"));
+        assert!(build_rs.contains("git describe"));
+        assert!(build_rs.contains("GIT_DESCRIBE"));
+        assert!(build_rs.contains("cargo:rerun-if-changed=.git/HEAD"));
+        assert!(build_rs.contains("cargo:rerun-if-changed=.git/refs/"));
+    }
+
+    #[test]
+    fn test_generate_build_rs_for_workspace_member() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_build_rs(temp_dir.path(), "../../.git", &mut FileWriter::default()).unwrap();
+
+        let build_rs = fs::read_to_string(temp_dir.path().join("build.rs")).unwrap();
+        assert!(build_rs.contains("cargo:rerun-if-changed=../../.git/HEAD"));
+        assert!(!build_rs.contains("{{GIT_DIR}}"));
+    }
+
+    #[test]
+    fn test_generate_workspace_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config();
+
+        generate_workspace_manifest(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("members = [\"crates/*\"]"));
+        assert!(cargo_toml.contains("[workspace.package]"));
+        assert!(cargo_toml.contains("license = \"MIT\""));
+        assert!(cargo_toml.contains("tool-core = { path = \"crates/tool-core\" }"));
+        assert!(cargo_toml.contains("clap = { version = \"*\", features = [\"derive\"] }"));
+        assert!(cargo_toml.contains("\nassert_cmd = \"*\""));
+        assert!(!cargo_toml.contains("[package]"));
+        assert!(cargo_toml.parse::<toml_edit::DocumentMut>().is_ok());
+    }
+
+    #[test]
+    fn test_generate_member_manifests_inherit_from_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config();
+        let cli_dir = temp_dir.path().join("tool-cli");
+        let core_dir = temp_dir.path().join("tool-core");
+        fs::create_dir_all(&cli_dir).unwrap();
+        fs::create_dir_all(&core_dir).unwrap();
+
+        generate_member_manifests("tool", &cli_dir, &core_dir, &config, &mut FileWriter::default()).unwrap();
+
+        let cli_toml = fs::read_to_string(cli_dir.join("Cargo.toml")).unwrap();
+        assert!(cli_toml.contains("name = \"tool-cli\""));
+        assert!(cli_toml.contains("[[bin]]\nname = \"tool\""));
+        assert!(cli_toml.contains("version.workspace = true"));
+        assert!(cli_toml.contains("tool-core.workspace = true"));
+        assert!(cli_toml.contains("clap.workspace = true"));
+        assert!(cli_toml.contains("[dev-dependencies]\ntempfile.workspace = true\nassert_cmd.workspace = true"));
+
+        let core_toml = fs::read_to_string(core_dir.join("Cargo.toml")).unwrap();
+        assert!(core_toml.contains("name = \"tool-core\""));
+        assert!(!core_toml.contains("build = "));
+    }
+}
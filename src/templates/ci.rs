@@ -0,0 +1,884 @@
+use super::FileWriter;
+use crate::config::{Component, ProjectKind};
+use eyre::Result;
+use std::path::Path;
+
+pub(super) fn generate_github_workflows(
+    project_name: &str,
+    target_dir: &Path,
+    kind: ProjectKind,
+    completions: bool,
+    components: &[Component],
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let workflows_dir = target_dir.join(".github").join("workflows");
+    writer.create_dir_all(&workflows_dir)?;
+
+    generate_github_ci_yml(&workflows_dir, kind, components, writer)?;
+    generate_github_release_yml(project_name, &workflows_dir, completions, writer)?;
+
+    Ok(())
+}
+
+/// Flag that makes cargo commands cover every member of a workspace
+fn workspace_flag(kind: ProjectKind) -> &'static str {
+    match kind {
+        ProjectKind::Package => "",
+        ProjectKind::Workspace => " --workspace",
+    }
+}
+
+fn generate_github_ci_yml(
+    workflows_dir: &Path,
+    kind: ProjectKind,
+    components: &[Component],
+    writer: &mut FileWriter,
+) -> Result<()> {
+    // Criterion's reports are kept as an artifact so runs can be compared
+    let bench_job = if components.contains(&Component::Bench) {
+        r#"
+
+  bench:
+    name: Benchmark
+    runs-on: ubuntu-latest
+    steps:
+    - uses: actions/checkout@v6
+
+    - name: Install Rust
+      uses: dtolnay/rust-toolchain@master
+      with:
+        toolchain: ${{ env.RUST_VERSION }}
+
+    - name: Cache Rust dependencies
+      uses: Swatinem/rust-cache@v2
+      with:
+        prefix-key: "v1-rust"
+
+    - name: Run benchmarks
+      run: cargo bench{{WORKSPACE}}
+
+    - name: Upload benchmark results
+      uses: actions/upload-artifact@v7
+      with:
+        name: criterion
+        path: target/criterion"#
+    } else {
+        ""
+    };
+    let ci_yml = r#"name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+env:
+  RUST_VERSION: 1.94.0
+  CARGO_TERM_COLOR: always
+
+jobs:
+  test:
+    name: Test
+    runs-on: ubuntu-latest
+    steps:
+    - uses: actions/checkout@v6
+
+    - name: Install Rust
+      uses: dtolnay/rust-toolchain@master
+      with:
+        toolchain: ${{ env.RUST_VERSION }}
+        components: rustfmt, clippy
+
+    - name: Cache Rust dependencies
+      uses: Swatinem/rust-cache@v2
+      with:
+        prefix-key: "v1-rust"
+
+    - name: Run tests
+      run: cargo test{{WORKSPACE}} --verbose
+
+    - name: Check formatting
+      run: cargo fmt{{FMT_ALL}} --check
+
+    - name: Run clippy
+      run: cargo clippy{{WORKSPACE}} -- -D warnings
+
+  build:
+    name: Build
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        include:
+          - os: ubuntu-latest
+          - os: macos-14  # Apple Silicon ARM
+    steps:
+    - uses: actions/checkout@v6
+
+    - name: Install Rust
+      uses: dtolnay/rust-toolchain@master
+      with:
+        toolchain: ${{ env.RUST_VERSION }}
+
+    - name: Cache Rust dependencies
+      uses: Swatinem/rust-cache@v2
+      with:
+        prefix-key: "v1-rust"
+        shared-key: ${{ matrix.os }}
+
+    - name: Build
+      run: cargo build --release{{WORKSPACE}} --verbose{{BENCH_JOB}}
+"#
+    .replace("{{BENCH_JOB}}", bench_job)
+    .replace("{{WORKSPACE}}", workspace_flag(kind))
+    .replace(
+        "{{FMT_ALL}}",
+        match kind {
+            ProjectKind::Package => "",
+            ProjectKind::Workspace => " --all",
+        },
+    );
+
+    writer.write_file(&workflows_dir.join("ci.yml"), &ci_yml)?;
+
+    Ok(())
+}
+
+fn generate_github_release_yml(
+    project_name: &str,
+    workflows_dir: &Path,
+    completions: bool,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    // Completions and man pages don't depend on the target, so a native build generates them even when cross-compiling
+    let (package_extras, archive_extras) = if completions {
+        (
+            r#"
+          cargo build --release
+          mkdir -p artifacts/completions artifacts/man
+          target/release/{{PROJECT}} completions bash > artifacts/completions/{{PROJECT}}.bash
+          target/release/{{PROJECT}} completions zsh > artifacts/completions/_{{PROJECT}}
+          target/release/{{PROJECT}} completions fish > artifacts/completions/{{PROJECT}}.fish
+          target/release/{{PROJECT}} man artifacts/man"#,
+            " completions man",
+        )
+    } else {
+        ("", "")
+    };
+    let release_yml = r#"name: Release
+
+on:
+  push:
+    tags:
+      - 'v*'
+
+env:
+  RUST_VERSION: 1.94.0
+  CARGO_TERM_COLOR: always
+
+permissions:
+  contents: write
+
+jobs:
+  build-linux:
+    runs-on: ubuntu-latest
+    container: debian:bookworm
+    strategy:
+      matrix:
+        include:
+          - target: x86_64-unknown-linux-gnu
+            suffix: linux-amd64
+            cross: false
+          - target: aarch64-unknown-linux-gnu
+            suffix: linux-arm64
+            cross: true
+    steps:
+      - name: Install build dependencies
+        run: |
+          apt-get update
+          apt-get install -y curl build-essential git pkg-config libssl-dev
+
+      - name: Install cross-compilation toolchain
+        if: matrix.cross
+        run: apt-get install -y gcc-aarch64-linux-gnu
+
+      - uses: actions/checkout@v6
+        with:
+          fetch-depth: 0
+          fetch-tags: true
+
+      - name: Mark workspace as safe for git
+        run: git config --global --add safe.directory "$GITHUB_WORKSPACE"
+
+      - name: Set GIT_DESCRIBE environment variable
+        run: |
+          GIT_DESCRIBE=$(git describe --tags --dirty --always)
+          echo "GIT_DESCRIBE=$GIT_DESCRIBE" >> $GITHUB_ENV
+
+      - name: Set up Rust
+        run: |
+          curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y --default-toolchain ${{ env.RUST_VERSION }}
+          echo "$HOME/.cargo/bin" >> $GITHUB_PATH
+          . "$HOME/.cargo/env"
+          rustup target add ${{ matrix.target }}
+
+      - name: Cache Rust dependencies
+        uses: Swatinem/rust-cache@v2
+        with:
+          prefix-key: "v1-rust"
+          shared-key: ${{ matrix.target }}
+
+      - name: Build for ${{ matrix.target }}
+        env:
+          CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER: ${{ matrix.cross && 'aarch64-linux-gnu-gcc' || '' }}
+        run: cargo build --release --target ${{ matrix.target }}
+
+      - name: Package binary
+        run: |
+          mkdir -p artifacts/
+          cp target/${{ matrix.target }}/release/{{PROJECT}} artifacts/{{PACKAGE_EXTRAS}}
+
+      - name: Archive artifacts
+        run: tar -czvf {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz -C artifacts {{PROJECT}}{{ARCHIVE_EXTRAS}}
+
+      - name: Generate checksum
+        run: sha256sum {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz > {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz.sha256
+
+      - name: Upload artifacts
+        uses: actions/upload-artifact@v7
+        with:
+          name: {{PROJECT}}-${{ matrix.suffix }}
+          path: |
+            {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz
+            {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz.sha256
+
+  build-macos:
+    runs-on: macos-14
+    strategy:
+      matrix:
+        include:
+          - target: x86_64-apple-darwin
+            suffix: macos-x86_64
+          - target: aarch64-apple-darwin
+            suffix: macos-arm64
+    steps:
+      - uses: actions/checkout@v6
+        with:
+          fetch-depth: 0
+          fetch-tags: true
+
+      - name: Set GIT_DESCRIBE environment variable
+        run: |
+          GIT_DESCRIBE=$(git describe --tags --dirty --always)
+          echo "GIT_DESCRIBE=$GIT_DESCRIBE" >> $GITHUB_ENV
+
+      - name: Set up Rust
+        run: |
+          curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y --default-toolchain ${{ env.RUST_VERSION }}
+          echo "$HOME/.cargo/bin" >> $GITHUB_PATH
+          rustup target add ${{ matrix.target }}
+
+      - name: Cache Rust dependencies
+        uses: Swatinem/rust-cache@v2
+        with:
+          prefix-key: "v1-rust"
+          shared-key: ${{ matrix.target }}
+
+      - name: Build for ${{ matrix.target }}
+        run: cargo build --release --target ${{ matrix.target }}
+
+      - name: Package binary
+        run: |
+          mkdir -p artifacts/
+          cp target/${{ matrix.target }}/release/{{PROJECT}} artifacts/{{PACKAGE_EXTRAS}}
+
+      - name: Archive artifacts
+        run: tar -czvf {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz -C artifacts {{PROJECT}}{{ARCHIVE_EXTRAS}}
+
+      - name: Generate checksum
+        run: shasum -a 256 {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz > {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz.sha256
+
+      - name: Upload artifacts
+        uses: actions/upload-artifact@v7
+        with:
+          name: {{PROJECT}}-${{ matrix.suffix }}
+          path: |
+            {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz
+            {{PROJECT}}-${{ github.ref_name }}-${{ matrix.suffix }}.tar.gz.sha256
+
+  create-release:
+    needs: [build-linux, build-macos]
+    runs-on: ubuntu-latest
+    steps:
+      - name: Download all artifacts
+        uses: actions/download-artifact@v8
+        with:
+          path: artifacts/
+
+      - name: Create Release
+        uses: softprops/action-gh-release@v2
+        with:
+          files: 'artifacts/**'
+        env:
+          GITHUB_TOKEN: ${{ secrets.GITHUB_TOKEN }}
+"#
+    .replace("{{PACKAGE_EXTRAS}}", package_extras)
+    .replace("{{ARCHIVE_EXTRAS}}", archive_extras)
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&workflows_dir.join("release.yml"), &release_yml)?;
+
+    Ok(())
+}
+
+pub(super) fn generate_otto_yml(
+    project_name: &str,
+    target_dir: &Path,
+    kind: ProjectKind,
+    completions: bool,
+    components: &[Component],
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let install_path = match kind {
+        ProjectKind::Package => ".".to_string(),
+        ProjectKind::Workspace => format!("crates/{}-cli", project_name),
+    };
+    let install_extras = if completions {
+        r#"
+      BIN="${CARGO_HOME:-$HOME/.cargo}/bin/{{PROJECT}}"
+      DATA_DIR="${XDG_DATA_HOME:-$HOME/.local/share}"
+      mkdir -p "$DATA_DIR/bash-completion/completions" "$DATA_DIR/zsh/site-functions" "$DATA_DIR/fish/vendor_completions.d"
+      "$BIN" completions bash > "$DATA_DIR/bash-completion/completions/{{PROJECT}}"
+      "$BIN" completions zsh > "$DATA_DIR/zsh/site-functions/_{{PROJECT}}"
+      "$BIN" completions fish > "$DATA_DIR/fish/vendor_completions.d/{{PROJECT}}.fish"
+      "$BIN" man "$DATA_DIR/man/man1"
+      echo "✅ Completions and man pages installed to $DATA_DIR""#
+    } else {
+        ""
+    };
+    let bench_task = if components.contains(&Component::Bench) {
+        r#"
+
+  # Run benchmarks
+  bench:
+    help: "Run the criterion benchmarks; reports land in target/criterion"
+    bash: |
+      cargo bench{{WORKSPACE}}"#
+    } else {
+        ""
+    };
+    let testing_tasks = if components.contains(&Component::Testing) {
+        r#"
+
+  # Run the property tests with more cases than cargo test does
+  proptest:
+    help: "Run the proptest round-trips with PROPTEST_CASES cases (default 10000)"
+    bash: |
+      PROPTEST_CASES="${PROPTEST_CASES:-10000}" cargo test{{WORKSPACE}} proptests
+
+  # Fuzz the config parser; needs a nightly toolchain and `cargo install cargo-fuzz`
+  fuzz:
+    help: "Fuzz the config parser for FUZZ_SECONDS (default 60)"
+    bash: |{{FUZZ_CD}}
+      cargo +nightly fuzz run parse_config -- -max_total_time="${FUZZ_SECONDS:-60}""#
+    } else {
+        ""
+    };
+    let fuzz_cd = match kind {
+        ProjectKind::Package => String::new(),
+        ProjectKind::Workspace => format!("\n      cd {}", install_path),
+    };
+    let otto_yml = r#"otto:
+  api: 1
+  tasks: [ci]
+  envs:
+    VERSION: "$(git describe --tags --always --dirty 2>/dev/null || echo 'dev')"
+
+tasks:
+  # Whitespace and convention linting
+  lint:
+    help: "Run whitespace and convention linting"
+    bash: |
+      whitespace -r
+      echo ""
+      echo "=== Deny _variable binding pattern ==="
+      if grep -rn --include='*.rs' -P '(\blet\s+(mut\s+)?|[(,]\s*|\|\s*|\bfor\s+)_[a-zA-Z]' {{SRC_DIRS}}; then
+        echo ""
+        echo "❌ Found _variable binding pattern."
+        echo "   Use _ to discard or use the variable — never _varname."
+        exit 1
+      fi
+      echo "✅ No _variable patterns found"
+
+  # Fail if any source file exceeds BLOAT_MAX_LINES (default 1500)
+  bloat:
+    help: "Fail if any source file exceeds BLOAT_MAX_LINES (default 1500)"
+    bash: |
+      LIMIT="${BLOAT_MAX_LINES:-1500}"
+      FAILED=0
+      echo "=== Checking file line counts (limit: $LIMIT) ==="
+      while IFS= read -r file; do
+        lines=$(wc -l < "$file")
+        if [ "$lines" -gt "$LIMIT" ]; then
+          delta=$((lines - LIMIT))
+          echo "  FAIL: $file ($lines lines, +$delta over limit)"
+          FAILED=1
+        fi
+      done < <(find . -type f \( -name "*.rs" -o -name "*.py" -o -name "*.js" -o -name "*.ts" \) \
+        -not -path "*/target/*" \
+        -not -path "*/node_modules/*" \
+        -not -path "*/.venv/*" \
+        -not -path "*/__pycache__/*" \
+        -not -path "*/dist/*" \
+        -not -path "*/build/*" \
+        -not -path "*/.git/*")
+      if [ "$FAILED" -eq 1 ]; then
+        echo ""
+        echo "Decompose these files into modules before proceeding."
+        exit 1
+      fi
+      echo "All files within $LIMIT line limit"
+
+  # Code quality checks
+  check:
+    help: "Run all quality checks (compile, clippy, format)"
+    bash: |
+      echo "=== Checking compilation ==="
+      cargo check{{WORKSPACE}} --all-targets --all-features
+      echo ""
+      echo "=== Running Clippy ==="
+      cargo clippy{{WORKSPACE}} --all-targets --all-features -- -D warnings
+      echo ""
+      echo "=== Checking format ==="
+      cargo fmt --all --check
+      echo ""
+      echo "✅ All checks passed!"
+
+  # Run tests
+  test:
+    help: "Run all tests"
+    bash: |
+      cargo test{{WORKSPACE}} --all-features{{BENCH_TASK}}{{TESTING_TASKS}}
+
+  # Run coverage tests and output data
+  cov:
+    help: "Run tests with coverage via llvm-cov"
+    after: [cov-report]
+    bash: |
+      echo "Running tests with coverage..."
+
+      JSON_FILE="$OTTO_TASK_DIR/coverage.json"
+
+      TEST_FAILED=0
+      if ! cargo llvm-cov{{WORKSPACE}} --all-features --json --output-path "$JSON_FILE" 2>&1; then
+        TEST_FAILED=1
+      fi
+
+      if [ ! -s "$JSON_FILE" ] || ! jq -e '.data[0].totals' "$JSON_FILE" >/dev/null 2>&1; then
+        echo "Tests failed - no coverage data generated"
+        exit 1
+      fi
+
+      cargo llvm-cov report --html --output-dir target/llvm-cov/html >/dev/null 2>&1 || true
+
+      otto_set_output "json_path" "$JSON_FILE"
+      otto_set_output "test_failed" "$TEST_FAILED"
+      otto_set_output "lines_pct" "$(jq -r '.data[0].totals.lines.percent // 0' "$JSON_FILE")"
+      otto_set_output "lines_cov" "$(jq -r '.data[0].totals.lines.covered // 0' "$JSON_FILE")"
+      otto_set_output "lines_tot" "$(jq -r '.data[0].totals.lines.count // 0' "$JSON_FILE")"
+      otto_set_output "funcs_pct" "$(jq -r '.data[0].totals.functions.percent // 0' "$JSON_FILE")"
+      otto_set_output "funcs_cov" "$(jq -r '.data[0].totals.functions.covered // 0' "$JSON_FILE")"
+      otto_set_output "funcs_tot" "$(jq -r '.data[0].totals.functions.count // 0' "$JSON_FILE")"
+      otto_set_output "regions_pct" "$(jq -r '.data[0].totals.regions.percent // 0' "$JSON_FILE")"
+
+  # Display coverage report (runs after cov)
+  cov-report:
+    help: "Display coverage report"
+    params:
+      --fail-under:
+        default: "0"
+        help: "Minimum line coverage percentage (0 = no threshold)"
+      --json:
+        default: "false"
+        help: "Output raw JSON coverage data"
+      --details:
+        default: "false"
+        help: "Show detailed per-file coverage"
+    bash: |
+      # Colors provided by otto builtins: RED, GREEN, YELLOW, BLUE, CYAN, MAGENTA, BOLD, DIM, NC
+
+      color_pct() {
+        local pct=$1
+        local int_pct=${pct%.*}
+        if [ "$int_pct" -ge 80 ]; then echo -e "${GREEN}${pct}%${NC}"
+        elif [ "$int_pct" -ge 60 ]; then echo -e "${YELLOW}${pct}%${NC}"
+        else echo -e "${RED}${pct}%${NC}"; fi
+      }
+
+      JSON_PATH=$(otto_get_input "cov.json_path")
+      TEST_FAILED=$(otto_get_input "cov.test_failed")
+      LINES_PCT=$(printf "%.1f" "$(otto_get_input "cov.lines_pct")")
+      LINES_COV=$(otto_get_input "cov.lines_cov")
+      LINES_TOT=$(otto_get_input "cov.lines_tot")
+      FUNCS_PCT=$(printf "%.1f" "$(otto_get_input "cov.funcs_pct")")
+      FUNCS_COV=$(otto_get_input "cov.funcs_cov")
+      FUNCS_TOT=$(otto_get_input "cov.funcs_tot")
+      REGIONS_PCT=$(printf "%.1f" "$(otto_get_input "cov.regions_pct")")
+
+      if [ "${json}" = "true" ]; then
+        cat "$JSON_PATH"
+        exit $TEST_FAILED
+      fi
+
+      if [ "${details}" = "true" ]; then
+        echo -e "${BOLD}${CYAN}Per-file Coverage:${NC}"
+        echo -e "${DIM}────────────────────────────────────────────────────────${NC}"
+        jq -r '.data[0].files[] | "\(.filename)|\(.summary.lines.percent // 0)|\(.summary.lines.covered // 0)|\(.summary.lines.count // 0)"' "$JSON_PATH" | \
+        sort -t'|' -k2 -n | while IFS='|' read -r file pct covered total; do
+          display=$(echo "$file" | sed 's|.*/src/||')
+          [ ${#display} -gt 40 ] && display="...${display: -37}"
+          int_pct=${pct%.*}
+          if [ "$int_pct" -ge 80 ]; then color=$GREEN
+          elif [ "$int_pct" -ge 60 ]; then color=$YELLOW
+          else color=$RED; fi
+          printf "  %-42s %b%6.1f%%%b %b(%d/%d)%b\n" "$display" "$color" "$pct" "$NC" "$DIM" "$covered" "$total" "$NC"
+        done
+        echo -e "${DIM}────────────────────────────────────────────────────────${NC}"
+        echo ""
+      fi
+
+      echo -e "${BOLD}${CYAN}Coverage Summary${NC}"
+      echo -e "${DIM}────────────────────────────────────────${NC}"
+      echo -e "  ${BOLD}Lines:${NC}     $(color_pct $LINES_PCT)  ${DIM}(${LINES_COV}/${LINES_TOT})${NC}"
+      echo -e "  ${BOLD}Functions:${NC} $(color_pct $FUNCS_PCT)  ${DIM}(${FUNCS_COV}/${FUNCS_TOT})${NC}"
+      echo -e "  ${BOLD}Regions:${NC}   $(color_pct $REGIONS_PCT)"
+      echo -e "${DIM}────────────────────────────────────────${NC}"
+      echo ""
+
+      THRESHOLD_FAILED=0
+      if [ "${fail_under}" != "0" ]; then
+        LINES_INT=${LINES_PCT%.*}
+        THRESHOLD_INT=${fail_under%.*}
+        if [ "$LINES_INT" -lt "$THRESHOLD_INT" ]; then
+          THRESHOLD_FAILED=1
+          echo -e "${RED}${BOLD}✗ Coverage ${LINES_PCT}% is below ${fail_under}% threshold${NC}"
+        else
+          echo -e "${GREEN}${BOLD}✓ Coverage ${LINES_PCT}% meets ${fail_under}% threshold${NC}"
+        fi
+        echo ""
+      fi
+
+      echo -e "${BLUE}Report:${NC} target/llvm-cov/html/index.html"
+
+      [ "$TEST_FAILED" = "1" ] || [ "$THRESHOLD_FAILED" = "1" ] && exit 1 || true
+
+  # Full CI pipeline
+  ci:
+    help: "Full CI pipeline (lint + bloat + check + test)"
+    before: [lint, bloat, check, test]
+    bash: |
+      echo "✅ All CI checks passed!"
+
+  # Build release binary
+  build:
+    help: "Build release binary"
+    bash: |
+      cargo build --release{{WORKSPACE}}
+      echo "✅ Release build complete"
+
+  # Wire up git hooks via pre-commit (run once after cloning)
+  setup:
+    help: "Install pre-commit hooks"
+    bash: |
+      pre-commit install
+      echo "✅ pre-commit hooks installed"
+
+  # Clean build artifacts
+  clean:
+    help: "Clean build artifacts"
+    bash: |
+      cargo clean
+      echo "✅ Build artifacts cleaned"
+
+  # Install locally
+  install:
+    help: "Install binary locally via cargo"
+    bash: |
+      cargo install --path {{INSTALL_PATH}}
+      echo "✅ Binary installed to ~/.cargo/bin"{{INSTALL_EXTRAS}}
+"#
+    .replace("{{BENCH_TASK}}", bench_task)
+    .replace("{{TESTING_TASKS}}", testing_tasks)
+    .replace("{{FUZZ_CD}}", &fuzz_cd)
+    .replace("{{WORKSPACE}}", workspace_flag(kind))
+    .replace(
+        "{{SRC_DIRS}}",
+        match kind {
+            ProjectKind::Package => "src/",
+            ProjectKind::Workspace => "crates/",
+        },
+    )
+    .replace("{{INSTALL_PATH}}", &install_path)
+    .replace("{{INSTALL_EXTRAS}}", install_extras)
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&target_dir.join(".otto.yml"), &otto_yml)?;
+
+    Ok(())
+}
+
+pub(super) fn generate_pre_commit_config(target_dir: &Path, writer: &mut FileWriter) -> Result<()> {
+    let config = r#"repos:
+  - repo: https://github.com/pre-commit/pre-commit-hooks
+    rev: v6.0.0
+    hooks:
+      - id: check-merge-conflict
+      - id: end-of-file-fixer
+      - id: trailing-whitespace
+      - id: check-yaml
+  - repo: https://github.com/doublify/pre-commit-rust
+    rev: v1.0
+    hooks:
+      - id: fmt
+      - id: clippy
+        args: ["--all-targets", "--all-features", "--", "-D", "warnings"]
+"#;
+
+    writer.write_file(&target_dir.join(".pre-commit-config.yaml"), config)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_github_ci_yml() {
+        let temp_dir = TempDir::new().unwrap();
+        let workflows_dir = temp_dir.path().join(".github").join("workflows");
+        fs::create_dir_all(&workflows_dir).unwrap();
+
+        let result = generate_github_ci_yml(&workflows_dir, ProjectKind::Package, &[], &mut FileWriter::default());
+        assert!(result.is_ok());
+
+        let ci_yml = fs::read_to_string(workflows_dir.join("ci.yml")).unwrap();
+
+        assert!(ci_yml.contains("name: CI"));
+        assert!(ci_yml.contains("branches: [main]"));
+        assert!(ci_yml.contains("RUST_VERSION: 1.94.0"));
+        assert!(ci_yml.contains("cargo test --verbose"));
+        assert!(ci_yml.contains("cargo fmt --check"));
+        assert!(ci_yml.contains("cargo clippy -- -D warnings"));
+        assert!(ci_yml.contains("macos-14"));
+        assert!(ci_yml.contains("cargo build --release --verbose"));
+        assert!(!ci_yml.contains("cargo bench"));
+    }
+
+    #[test]
+    fn test_generate_github_ci_yml_with_bench_job() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_github_ci_yml(
+            temp_dir.path(),
+            ProjectKind::Workspace,
+            &[Component::Bench],
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let ci_yml = fs::read_to_string(temp_dir.path().join("ci.yml")).unwrap();
+        assert!(ci_yml.contains("run: cargo bench --workspace\n"));
+        assert!(ci_yml.contains("uses: actions/upload-artifact@v7"));
+        assert!(ci_yml.contains("path: target/criterion\n"));
+        assert!(!ci_yml.contains("{{BENCH_JOB}}"));
+    }
+
+    #[test]
+    fn test_generate_github_release_yml() {
+        let temp_dir = TempDir::new().unwrap();
+        let workflows_dir = temp_dir.path().join(".github").join("workflows");
+        fs::create_dir_all(&workflows_dir).unwrap();
+        let project_name = "test-release";
+
+        let result = generate_github_release_yml(project_name, &workflows_dir, false, &mut FileWriter::default());
+        assert!(result.is_ok());
+
+        let release_yml = fs::read_to_string(workflows_dir.join("release.yml")).unwrap();
+
+        assert!(release_yml.contains("name: Release"));
+        assert!(release_yml.contains("- 'v*'"));
+        assert!(release_yml.contains("contents: write"));
+        assert!(release_yml.contains("x86_64-unknown-linux-gnu"));
+        assert!(release_yml.contains("aarch64-unknown-linux-gnu"));
+        assert!(release_yml.contains("x86_64-apple-darwin"));
+        assert!(release_yml.contains("aarch64-apple-darwin"));
+        assert!(release_yml.contains("softprops/action-gh-release@v2"));
+        // Verify project name was substituted
+        assert!(release_yml.contains("test-release"));
+        assert!(!release_yml.contains("{{PROJECT}}"));
+        // Verify no Docker job
+        assert!(!release_yml.contains("docker"));
+        assert!(!release_yml.contains("Dockerfile"));
+        assert!(!release_yml.contains("completions"));
+    }
+
+    #[test]
+    fn test_generate_github_release_yml_packages_completions() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_github_release_yml("tool", temp_dir.path(), true, &mut FileWriter::default()).unwrap();
+
+        let release_yml = fs::read_to_string(temp_dir.path().join("release.yml")).unwrap();
+        // Both the linux and macos jobs package them
+        assert_eq!(release_yml.matches("target/release/tool man artifacts/man").count(), 2);
+        assert!(release_yml.contains("target/release/tool completions zsh > artifacts/completions/_tool"));
+        assert!(release_yml.contains("-C artifacts tool completions man\n"));
+        assert!(!release_yml.contains("EXTRAS}}"));
+    }
+
+    #[test]
+    fn test_generate_github_workflows() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-workflows";
+
+        let result = generate_github_workflows(
+            project_name,
+            temp_dir.path(),
+            ProjectKind::Package,
+            false,
+            &[],
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        assert!(temp_dir.path().join(".github/workflows/ci.yml").exists());
+        assert!(temp_dir.path().join(".github/workflows/release.yml").exists());
+    }
+
+    #[test]
+    fn test_generate_github_ci_yml_for_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_github_ci_yml(temp_dir.path(), ProjectKind::Workspace, &[], &mut FileWriter::default()).unwrap();
+
+        let ci_yml = fs::read_to_string(temp_dir.path().join("ci.yml")).unwrap();
+        assert!(ci_yml.contains("cargo test --workspace --verbose"));
+        assert!(ci_yml.contains("cargo fmt --all --check"));
+        assert!(ci_yml.contains("cargo clippy --workspace -- -D warnings"));
+        assert!(ci_yml.contains("cargo build --release --workspace --verbose"));
+    }
+
+    #[test]
+    fn test_generate_otto_yml_for_package() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_otto_yml(
+            "tool",
+            temp_dir.path(),
+            ProjectKind::Package,
+            false,
+            &[],
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let otto_yml = fs::read_to_string(temp_dir.path().join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("cargo test --all-features"));
+        assert!(otto_yml.contains("_[a-zA-Z]' src/; then"));
+        assert!(otto_yml.contains("cargo install --path .\n"));
+        assert!(!otto_yml.contains("cargo bench"));
+        assert!(!otto_yml.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_otto_yml_with_bench_task() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_otto_yml(
+            "tool",
+            temp_dir.path(),
+            ProjectKind::Package,
+            false,
+            &[Component::Bench],
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let otto_yml = fs::read_to_string(temp_dir.path().join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("  bench:\n"));
+        assert!(otto_yml.contains("      cargo bench\n"));
+        assert!(!otto_yml.contains("cargo +nightly fuzz"));
+        assert!(!otto_yml.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_otto_yml_with_testing_tasks() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_otto_yml(
+            "tool",
+            temp_dir.path(),
+            ProjectKind::Workspace,
+            false,
+            &[Component::Testing],
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let otto_yml = fs::read_to_string(temp_dir.path().join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("cargo test --workspace proptests\n"));
+        assert!(otto_yml.contains(
+            "    bash: |\n      cd crates/tool-cli\n      cargo +nightly fuzz run parse_config -- -max_total_time"
+        ));
+        assert!(!otto_yml.contains("cargo bench"));
+        assert!(!otto_yml.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_otto_yml_for_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_otto_yml(
+            "tool",
+            temp_dir.path(),
+            ProjectKind::Workspace,
+            false,
+            &[],
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let otto_yml = fs::read_to_string(temp_dir.path().join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("cargo check --workspace --all-targets --all-features"));
+        assert!(otto_yml.contains("cargo clippy --workspace --all-targets"));
+        assert!(otto_yml.contains("cargo test --workspace --all-features"));
+        assert!(otto_yml.contains("cargo llvm-cov --workspace --all-features"));
+        assert!(otto_yml.contains("_[a-zA-Z]' crates/; then"));
+        assert!(otto_yml.contains("cargo install --path crates/tool-cli"));
+        assert!(!otto_yml.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_otto_yml_installs_completions() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_otto_yml(
+            "tool",
+            temp_dir.path(),
+            ProjectKind::Package,
+            true,
+            &[],
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let otto_yml = fs::read_to_string(temp_dir.path().join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("BIN=\"${CARGO_HOME:-$HOME/.cargo}/bin/tool\""));
+        assert!(otto_yml.contains("\"$BIN\" completions bash > \"$DATA_DIR/bash-completion/completions/tool\""));
+        assert!(otto_yml.contains("\"$BIN\" man \"$DATA_DIR/man/man1\""));
+        assert!(!otto_yml.contains("{{"));
+    }
+}
@@ -0,0 +1,280 @@
+use super::FileWriter;
+use super::source::{COMPLETIONS_VARIANTS, CONFIG_VARIANT};
+use crate::cli_spec::{BUILTIN_COMMANDS, identifier, pascal_case};
+use eyre::Result;
+use std::path::Path;
+
+/// A subcommand of the generated CLI with the Rust names derived from its command-line name
+#[derive(Debug)]
+pub(super) struct Subcommand {
+    /// Name on the command line, e.g. `dry-run`
+    pub name: String,
+    /// Handler module under src/commands/, e.g. `dry_run`
+    pub module: String,
+    /// Variant of the generated `Command` enum, e.g. `DryRun`
+    pub variant: String,
+    /// Arguments after the name that make a valid invocation, used by generated tests
+    pub sample_args: Vec<String>,
+}
+
+impl Subcommand {
+    pub(super) fn new(name: &str, sample_args: Vec<String>) -> Result<Self> {
+        if BUILTIN_COMMANDS.contains(&name) {
+            return Err(eyre::eyre!("Subcommand '{}' is reserved by the generated CLI", name));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            module: identifier("subcommand", name)?,
+            variant: pascal_case(name),
+            sample_args,
+        })
+    }
+}
+
+/// Check the configured subcommand names and derive their module and variant names
+pub(super) fn parse_subcommands(names: &[String]) -> Result<Vec<Subcommand>> {
+    let mut subcommands: Vec<Subcommand> = Vec::with_capacity(names.len());
+
+    for name in names {
+        let subcommand = Subcommand::new(name, vec!["--dry-run".to_string()])?;
+        if subcommands.iter().any(|existing| existing.name == *name) {
+            return Err(eyre::eyre!("Subcommand '{}' is listed more than once", name));
+        }
+        subcommands.push(subcommand);
+    }
+
+    Ok(subcommands)
+}
+
+/// `Command` variants every generated CLI has: `config`, plus hidden `completions` and `man` when enabled
+pub(super) fn builtin_variants(completions: bool) -> String {
+    let mut variants = CONFIG_VARIANT.to_string();
+    if completions {
+        variants.push_str(COMPLETIONS_VARIANTS);
+    }
+    variants
+}
+
+/// Match arms handling `builtin_variants`, for a `match` on `&Command` with `config` in scope
+pub(super) fn builtin_arms(completions: bool) -> String {
+    let mut arms = "        Command::Config(command) => crate::config::run_command(command, config),\n".to_string();
+    if completions {
+        arms.push_str(
+            r#"        Command::Completions { shell } => {
+            crate::cli::print_completions(*shell);
+            Ok(())
+        }
+        Command::Man { dir } => crate::cli::write_man_pages(dir),
+"#,
+        );
+    }
+    arms
+}
+
+/// The `Command` enum added to cli.rs, one variant per subcommand taking its handler's arguments, plus the built-ins
+pub(super) fn command_enum(subcommands: &[Subcommand], completions: bool) -> String {
+    let mut variants = String::new();
+    for subcommand in subcommands {
+        variants.push_str(&format!(
+            "    /// Run the {} subcommand\n    {}(crate::commands::{}::{}Args),\n",
+            subcommand.name, subcommand.variant, subcommand.module, subcommand.variant
+        ));
+    }
+
+    variants.push_str(&builtin_variants(completions));
+
+    format!("\n#[derive(Subcommand)]\npub enum Command {{\n{}}}\n", variants)
+}
+
+/// Statement in `run_application`/`run` handing the parsed subcommand to its handler
+pub(super) fn dispatch() -> &'static str {
+    "    // Dispatch to the subcommand's handler\n    commands::dispatch(&cli.command, config)"
+}
+
+/// src/commands/mod.rs declaring the handler modules and dispatching each `Command` variant to one
+pub(super) fn generate_commands_mod(
+    subcommands: &[Subcommand],
+    completions: bool,
+    commands_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let mut modules = String::new();
+    let mut arms = String::new();
+    for subcommand in subcommands {
+        modules.push_str(&format!("pub mod {};\n", subcommand.module));
+        arms.push_str(&format!(
+            "        Command::{}(args) => {}::run(args, config),\n",
+            subcommand.variant, subcommand.module
+        ));
+    }
+    arms.push_str(&builtin_arms(completions));
+
+    let mod_rs = format!(
+        r#"//! One handler module per subcommand
+
+{}
+use crate::cli::Command;
+use crate::config::Config;
+use eyre::Result;
+
+/// Run the handler for the parsed subcommand
+pub fn dispatch(command: &Command, config: &Config) -> Result<()> {{
+    match command {{
+{}    }}
+}}
+"#,
+        modules, arms
+    );
+
+    writer.write_file(&commands_dir.join("mod.rs"), &mod_rs)
+}
+
+/// src/commands/mod.rs plus one handler module per subcommand
+pub(super) fn generate_commands(
+    project_name: &str,
+    subcommands: &[Subcommand],
+    completions: bool,
+    src_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let commands_dir = src_dir.join("commands");
+    writer.create_dir_all(&commands_dir)?;
+    generate_commands_mod(subcommands, completions, &commands_dir, writer)?;
+
+    for subcommand in subcommands {
+        let handler_rs = r#"use crate::config::Config;
+use clap::Args;
+use colored::*;
+use eyre::Result;
+use log::info;
+
+/// Arguments for `{{PROJECT}} {{NAME}}`
+#[derive(Debug, Args)]
+pub struct {{VARIANT}}Args {
+    /// Only show what would be done
+    #[arg(long, help = "Only show what would be done")]
+    pub dry_run: bool,
+}
+
+/// Handle `{{PROJECT}} {{NAME}}`
+pub fn run(args: &{{VARIANT}}Args, config: &Config) -> Result<()> {
+    info!("Running {{NAME}}");
+
+    if args.dry_run {
+        println!("{} Would run {{NAME}} for {}", "🔍".yellow(), config.name);
+        return Ok(());
+    }
+
+    println!("{} Ran {{NAME}} for {}", "✓".green(), config.name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_{{MODULE}}_dry_run() {
+        let args = {{VARIANT}}Args { dry_run: true };
+        assert!(run(&args, &Config::default()).is_ok());
+    }
+}
+"#
+        .replace("{{PROJECT}}", project_name)
+        .replace("{{NAME}}", &subcommand.name)
+        .replace("{{MODULE}}", &subcommand.module)
+        .replace("{{VARIANT}}", &subcommand.variant);
+
+        writer.write_file(&commands_dir.join(format!("{}.rs", subcommand.module)), &handler_rs)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_subcommands_derives_rust_names() {
+        let subcommands = parse_subcommands(&names(&["init", "dry-run", "v2-sync"])).unwrap();
+
+        let derived: Vec<(&str, &str)> = subcommands
+            .iter()
+            .map(|subcommand| (subcommand.module.as_str(), subcommand.variant.as_str()))
+            .collect();
+        assert_eq!(
+            derived,
+            [("init", "Init"), ("dry_run", "DryRun"), ("v2_sync", "V2Sync")]
+        );
+    }
+
+    #[test]
+    fn test_parse_subcommands_rejects_bad_names() {
+        for bad in [
+            "Init",
+            "2fast",
+            "with space",
+            "trailing-",
+            "double--dash",
+            "type",
+            "help",
+            "config",
+        ] {
+            assert!(parse_subcommands(&names(&[bad])).is_err(), "accepted '{}'", bad);
+        }
+
+        let error = parse_subcommands(&names(&["init", "init"])).unwrap_err();
+        assert!(error.to_string().contains("more than once"));
+    }
+
+    #[test]
+    fn test_generate_commands_writes_one_module_per_subcommand() {
+        let temp_dir = TempDir::new().unwrap();
+        let subcommands = parse_subcommands(&names(&["init", "dry-run"])).unwrap();
+
+        generate_commands("tool", &subcommands, false, temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let mod_rs = fs::read_to_string(temp_dir.path().join("commands/mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod init;\npub mod dry_run;\n"));
+        assert!(mod_rs.contains("Command::DryRun(args) => dry_run::run(args, config),"));
+        assert!(mod_rs.contains("Command::Config(command) => crate::config::run_command(command, config),"));
+
+        let handler_rs = fs::read_to_string(temp_dir.path().join("commands/dry_run.rs")).unwrap();
+        assert!(handler_rs.contains("pub struct DryRunArgs"));
+        assert!(handler_rs.contains("pub fn run(args: &DryRunArgs, config: &Config) -> Result<()>"));
+        assert!(handler_rs.contains("/// Handle `tool dry-run`"));
+        assert!(!handler_rs.contains("{{"));
+    }
+
+    #[test]
+    fn test_command_enum() {
+        let subcommands = parse_subcommands(&names(&["init", "dry-run"])).unwrap();
+
+        let command_enum = command_enum(&subcommands, false);
+        assert!(command_enum.contains("pub enum Command {"));
+        assert!(command_enum.ends_with("    Config(ConfigCommand),\n}\n"));
+        assert!(command_enum.contains("DryRun(crate::commands::dry_run::DryRunArgs),"));
+
+        let command_enum = super::command_enum(&subcommands, true);
+        assert!(command_enum.contains("    Config(ConfigCommand),\n    /// Print shell completions\n"));
+        assert!(command_enum.ends_with("        dir: PathBuf,\n    },\n}\n"));
+    }
+
+    #[test]
+    fn test_builtin_arms() {
+        assert_eq!(builtin_arms(false).lines().count(), 1);
+
+        let arms = builtin_arms(true);
+        assert!(arms.contains("Command::Completions { shell } => {"));
+        assert!(arms.ends_with("Command::Man { dir } => crate::cli::write_man_pages(dir),\n"));
+    }
+}
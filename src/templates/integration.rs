@@ -0,0 +1,146 @@
+use super::FileWriter;
+use crate::config::ConfigFormat;
+use eyre::Result;
+use std::path::Path;
+
+/// tests/cli.rs, running the built binary with assert_cmd in a throwaway HOME
+pub(super) fn generate_cli_tests(
+    project_name: &str,
+    format: ConfigFormat,
+    package_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    // Config file fixture as a Rust string literal
+    let fixture = match format {
+        ConfigFormat::Yaml => r#""name: Fixture User\n""#,
+        ConfigFormat::Toml => r#""name = \"Fixture User\"\n""#,
+        ConfigFormat::Json => r##"r#"{"name": "Fixture User"}"#"##,
+    };
+
+    let tests_dir = package_dir.join("tests");
+    writer.create_dir_all(&tests_dir)?;
+
+    let cli_rs = r#"//! End-to-end tests running the {{PROJECT}} binary
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// The binary with HOME and the XDG dirs inside `home`, away from the user's config and logs
+fn command(home: &TempDir) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_{{PROJECT}}"));
+    command
+        .current_dir(home.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .env("XDG_DATA_HOME", home.path().join(".local/share"));
+    command
+}
+
+#[test]
+fn test_help() {
+    let home = TempDir::new().unwrap();
+    command(&home)
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage:"))
+        .stdout(predicate::str::contains("--config"));
+}
+
+#[test]
+fn test_version_comes_from_git_describe() {
+    // build.rs sets GIT_DESCRIBE for the tests as well as the binary
+    let home = TempDir::new().unwrap();
+    let expected = format!("{{PROJECT}} {}\n", env!("GIT_DESCRIBE"));
+    command(&home)
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(expected));
+}
+
+#[test]
+fn test_run_with_config_fixture() {
+    let home = TempDir::new().unwrap();
+    let config_file = home.path().join("fixture.{{EXTENSION}}");
+    fs::write(&config_file, {{FIXTURE}}).unwrap();
+
+    command(&home)
+        .arg("--config")
+        .arg(&config_file)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixture User"));
+}
+
+#[test]
+fn test_broken_default_config_is_reported() {
+    // ./{{PROJECT}}.{{EXTENSION}} is the fallback search path; a broken one is skipped with a warning
+    let home = TempDir::new().unwrap();
+    fs::write(home.path().join("{{PROJECT}}.{{EXTENSION}}"), "not: [valid\n").unwrap();
+
+    command(&home)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Failed to load config from"));
+}
+
+#[test]
+fn test_missing_config_is_an_error() {
+    let home = TempDir::new().unwrap();
+    command(&home)
+        .args(["--config", "missing.{{EXTENSION}}", "config", "show"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to load configuration"));
+}
+"#
+    .replace("{{FIXTURE}}", fixture)
+    .replace("{{EXTENSION}}", format.extension())
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&tests_dir.join("cli.rs"), &cli_rs)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_cli_tests() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_cli_tests(
+            "my-tool",
+            ConfigFormat::Yaml,
+            temp_dir.path(),
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let cli_rs = fs::read_to_string(temp_dir.path().join("tests/cli.rs")).unwrap();
+        assert!(cli_rs.contains("Command::new(env!(\"CARGO_BIN_EXE_my-tool\"))"));
+        assert!(cli_rs.contains("format!(\"my-tool {}\\n\", env!(\"GIT_DESCRIBE\"))"));
+        assert!(cli_rs.contains("fs::write(&config_file, \"name: Fixture User\\n\").unwrap();"));
+        assert!(cli_rs.contains("\"missing.yml\""));
+        assert!(cli_rs.contains("fs::write(home.path().join(\"my-tool.yml\"), \"not: [valid\\n\").unwrap();"));
+        assert!(!cli_rs.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_cli_tests_json_fixture() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_cli_tests("tool", ConfigFormat::Json, temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let cli_rs = fs::read_to_string(temp_dir.path().join("tests/cli.rs")).unwrap();
+        assert!(cli_rs.contains("r#\"{\"name\": \"Fixture User\"}\"#"));
+        assert!(cli_rs.contains("join(\"fixture.json\")"));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::config::LoggingBackend;
+
+/// Creates `<data dir>/<project>/logs` and opens the rotating log file in it; shared by every backend
+const LOG_DIR: &str = r#"    // Create log directory
+    let log_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("{{PROJECT}}")
+        .join("logs");
+
+    fs::create_dir_all(&log_dir).context("Failed to create log directory")?;
+
+    let log_file = log_dir.join("{{PROJECT}}.log");
+    let log_writer = RotatingFile::open(&log_file, config)?;
+"#;
+
+/// `setup_logging(level, config)` for main.rs, initializing `backend` at the level chosen with -v/-q
+pub(super) fn setup_logging_fn(backend: LoggingBackend, project_name: &str) -> String {
+    let init = match backend {
+        LoggingBackend::EnvLogger => {
+            r#"
+    // Setup env_logger with file output; RUST_LOG overrides -v/-q
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .target(env_logger::Target::Pipe(Box::new(log_writer)))
+        .init();
+"#
+        }
+        LoggingBackend::Fern => {
+            r#"
+    // Setup fern writing the same records to stderr and the log file
+    fern::Dispatch::new()
+        .format(|out, message, record| {
+            out.finish(format_args!(
+                "{} {} {}: {}",
+                humantime::format_rfc3339_seconds(std::time::SystemTime::now()),
+                record.level(),
+                record.target(),
+                message
+            ))
+        })
+        .level(level)
+        .chain(std::io::stderr())
+        .chain(Box::new(log_writer) as Box<dyn std::io::Write + Send>)
+        .apply()
+        .context("Failed to initialize logging")?;
+"#
+        }
+        LoggingBackend::Tracing => {
+            r#"
+    // Setup tracing-subscriber writing to the log file; RUST_LOG overrides -v/-q, log records are forwarded
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(level.to_string().to_lowercase()));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::sync::Mutex::new(log_writer))
+        .with_ansi(false)
+        .try_init()
+        .map_err(|e| eyre::eyre!(e))
+        .context("Failed to initialize logging")?;
+"#
+        }
+    };
+
+    format!(
+        r#"fn setup_logging(level: log::LevelFilter, config: &LogConfig) -> Result<()> {{
+{}{}
+    info!("Logging initialized, writing to: {{}}", log_file.display());
+    Ok(())
+}}"#,
+        LOG_DIR, init
+    )
+    .replace("{{PROJECT}}", project_name)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_logging_fn_per_backend() {
+        let env_logger = setup_logging_fn(LoggingBackend::EnvLogger, "tool");
+        assert!(
+            env_logger.starts_with("fn setup_logging(level: log::LevelFilter, config: &LogConfig) -> Result<()> {")
+        );
+        assert!(env_logger.contains("RotatingFile::open(&log_file, config)?"));
+        assert!(env_logger.contains(".join(\"tool\")"));
+        assert!(env_logger.contains(".filter_level(level)"));
+        assert!(env_logger.contains("env_logger::Target::Pipe(Box::new(log_writer))"));
+
+        let fern = setup_logging_fn(LoggingBackend::Fern, "tool");
+        assert!(fern.contains(".chain(std::io::stderr())"));
+        assert!(fern.contains(".chain(Box::new(log_writer) as Box<dyn std::io::Write + Send>)"));
+        assert!(!fern.contains("env_logger"));
+
+        let tracing = setup_logging_fn(LoggingBackend::Tracing, "tool");
+        assert!(tracing.contains(".with_writer(std::sync::Mutex::new(log_writer))"));
+        assert!(tracing.contains("EnvFilter::try_from_default_env()"));
+        assert!(!tracing.contains("{{PROJECT}}"));
+    }
+}
@@ -0,0 +1,953 @@
+use crate::cli_spec::{CliSpec, SPEC_FILE};
+use crate::config::{Component, Config, ProjectKind};
+use crate::conflict::{self, ConflictPolicy, ConflictResolver, Merged};
+use crate::transaction::Journal;
+use crate::workspace::Workspace;
+use colored::*;
+use eyre::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod bench;
+mod cargo;
+mod ci;
+mod commands;
+mod integration;
+mod logging;
+mod source;
+mod spec;
+mod testing;
+
+use bench::generate_bench;
+use cargo::{
+    generate_build_rs, generate_cargo_toml, generate_clippy_toml, generate_member_manifests,
+    generate_workspace_manifest,
+};
+use ci::{generate_github_workflows, generate_otto_yml, generate_pre_commit_config};
+use commands::{generate_commands, parse_subcommands};
+use integration::generate_cli_tests;
+use source::{
+    generate_cli_rs, generate_config_rs, generate_config_tests_rs, generate_core_lib_rs, generate_lib_rs,
+    generate_main_rs, generate_sample_config,
+};
+use spec::{generate_spec_cli, spec_subcommands};
+use testing::{generate_fuzz, generate_proptests};
+
+/// Writes generated files, journaling every change so a failed scaffold can be rolled back
+#[derive(Debug, Default)]
+pub struct FileWriter {
+    root: PathBuf,
+    resolver: ConflictResolver,
+    pub journal: Journal,
+}
+
+impl FileWriter {
+    /// Writer for a project rooted at `root`; `resolver` decides what happens to files that already exist
+    pub fn new(root: &Path, resolver: ConflictResolver) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            resolver,
+            journal: Journal::default(),
+        }
+    }
+
+    /// Write content to a file, applying the conflict policy for its path if it already exists
+    fn write_file(&mut self, path: &Path, content: &str) -> Result<()> {
+        if !path.exists() {
+            return self.write_journaled(path, content);
+        }
+
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let display = relative.display().to_string();
+        let mut policy = self.resolver.policy_for(relative);
+        if policy == ConflictPolicy::Prompt {
+            policy = conflict::prompt_policy(&display)?;
+        }
+        log::info!("{} exists, applying conflict policy {:?}", display, policy);
+
+        match policy {
+            ConflictPolicy::Skip | ConflictPolicy::Prompt => {
+                println!("{} Skipping existing file: {}", "⚠".yellow(), display);
+                Ok(())
+            }
+            ConflictPolicy::Overwrite => self.write_journaled(path, content),
+            ConflictPolicy::Backup => {
+                let backup = conflict::backup_path(path);
+                self.journal.record(&backup)?;
+                fs::copy(path, &backup).context(format!("Failed to back up {}", path.display()))?;
+                let backup_display = backup.strip_prefix(&self.root).unwrap_or(&backup).display();
+                println!("{} Backed up {} to {}", "✓".green(), display, backup_display);
+                self.write_journaled(path, content)
+            }
+            ConflictPolicy::Merge => {
+                let existing = fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+                match conflict::merge(path, &existing, content) {
+                    Merged::Clean(merged) => {
+                        println!("{} Merged into existing file: {}", "✓".green(), display);
+                        self.write_journaled(path, &merged)
+                    }
+                    Merged::Conflicted(merged) => {
+                        println!(
+                            "{} Merged with conflicts, resolve the markers in: {}",
+                            "⚠".yellow(),
+                            display
+                        );
+                        self.write_journaled(path, &merged)
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_journaled(&mut self, path: &Path, content: &str) -> Result<()> {
+        self.journal.record(path)?;
+        fs::write(path, content).context(format!("Failed to write {}", path.display()))
+    }
+
+    fn create_dir_all(&mut self, path: &Path) -> Result<()> {
+        self.journal.record_dir(path)?;
+        fs::create_dir_all(path).context(format!("Failed to create {}", path.display()))
+    }
+}
+
+pub fn generate_project(
+    project_name: &str,
+    target_dir: &Path,
+    author: &str,
+    config: &Config,
+    include_deps: bool,
+    workspace: Option<&Workspace>,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    match config.template.kind {
+        ProjectKind::Package => {
+            // Create src directory
+            let src_dir = target_dir.join("src");
+            writer.create_dir_all(&src_dir)?;
+
+            // Generate Cargo.toml
+            generate_cargo_toml(
+                project_name,
+                target_dir,
+                author,
+                config,
+                include_deps,
+                workspace,
+                writer,
+            )?;
+
+            // Generate build.rs, watching the enclosing workspace's repo when joining one
+            let git_dir = match workspace {
+                Some(workspace) => workspace.git_dir_from(target_dir)?,
+                None => ".git".to_string(),
+            };
+            generate_build_rs(target_dir, &git_dir, writer)?;
+
+            // Generate source files
+            generate_sources(project_name, project_name, &src_dir, config, writer)?;
+
+            // Generate tests/cli.rs
+            generate_cli_tests(project_name, config.template.config_format, target_dir, writer)?;
+            if config.template.has_component(Component::Bench) {
+                generate_bench(
+                    project_name,
+                    config.template.config_format,
+                    project_name,
+                    target_dir,
+                    writer,
+                )?;
+            }
+            if config.template.has_component(Component::Testing) {
+                generate_fuzz(
+                    project_name,
+                    config.template.config_format,
+                    project_name,
+                    target_dir,
+                    writer,
+                )?;
+            }
+        }
+        ProjectKind::Workspace => generate_workspace_crates(project_name, target_dir, author, config, writer)?,
+    }
+
+    // Keep the CLI spec with the project so `scaffold regen-cli` can rebuild cli.rs from it
+    if let Some(spec_path) = &config.template.cli.spec {
+        copy_spec(spec_path, &target_dir.join(SPEC_FILE), writer)?;
+    }
+
+    // Generate sample config file
+    generate_sample_config(project_name, config.template.config_format, target_dir, writer)?;
+
+    // Generate clippy.toml for lint config
+    generate_clippy_toml(target_dir, writer)?;
+
+    if let Some(workspace) = workspace {
+        // CI, otto and pre-commit are configured once at the workspace root
+        println!(
+            "{} Skipping CI, otto and pre-commit files (using the workspace's at {})",
+            "✓".green(),
+            workspace.root.display()
+        );
+    } else {
+        // Generate GitHub Actions workflows
+        generate_github_workflows(
+            project_name,
+            target_dir,
+            config.template.kind,
+            config.template.create_completions,
+            &config.template.components,
+            writer,
+        )?;
+
+        // Generate .otto.yml for CI
+        generate_otto_yml(
+            project_name,
+            target_dir,
+            config.template.kind,
+            config.template.create_completions,
+            &config.template.components,
+            writer,
+        )?;
+
+        // Generate .pre-commit-config.yaml
+        generate_pre_commit_config(target_dir, writer)?;
+    }
+
+    println!("{} Generated all project files", "✓".green());
+    Ok(())
+}
+
+/// main.rs, cli.rs, config.rs and, when configured, lib.rs and the subcommand handlers of the package `package`
+///
+/// With `template.cli.spec` set, cli.rs and the handlers come from that spec instead.
+fn generate_sources(
+    project_name: &str,
+    package: &str,
+    src_dir: &Path,
+    config: &Config,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let spec = config.template.cli.spec.as_deref().map(CliSpec::load).transpose()?;
+    let subcommands = match &spec {
+        Some(_) if !config.template.cli.subcommands.is_empty() => {
+            return Err(eyre::eyre!(
+                "Set either template.cli.subcommands or template.cli.spec, not both"
+            ));
+        }
+        Some(spec) => spec_subcommands(spec)?,
+        None => parse_subcommands(&config.template.cli.subcommands)?,
+    };
+    let lib = config.template.create_lib.then_some(package);
+    let completions = config.template.create_completions;
+
+    generate_main_rs(
+        project_name,
+        src_dir,
+        lib,
+        &subcommands,
+        config.template.logging,
+        completions,
+        writer,
+    )?;
+    if lib.is_some() {
+        generate_lib_rs(project_name, src_dir, &subcommands, completions, writer)?;
+    }
+    match &spec {
+        Some(spec) => generate_spec_cli(project_name, spec, &subcommands, completions, src_dir, writer)?,
+        None => {
+            generate_cli_rs(project_name, &subcommands, completions, src_dir, writer)?;
+            if !subcommands.is_empty() {
+                generate_commands(project_name, &subcommands, completions, src_dir, writer)?;
+            }
+        }
+    }
+    let testing = config.template.has_component(Component::Testing);
+    generate_config_rs(project_name, config.template.config_format, testing, src_dir, writer)?;
+    generate_config_tests_rs(config.template.config_format, src_dir, writer)?;
+    if testing {
+        generate_proptests(config.template.config_format, src_dir, writer)?;
+    }
+
+    Ok(())
+}
+
+fn copy_spec(spec_path: &Path, destination: &Path, writer: &mut FileWriter) -> Result<()> {
+    let same_file = fs::canonicalize(spec_path).ok() == fs::canonicalize(destination).ok() && destination.exists();
+    if same_file {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(spec_path).context(format!("Failed to read CLI spec {}", spec_path.display()))?;
+    writer.write_file(destination, &content)
+}
+
+/// Rebuild cli.rs, its tests and commands/mod.rs of the CLI package in `dir` from the spec at `spec_path`,
+/// adding stubs for new commands and leaving existing handler code alone
+pub fn regenerate_cli(spec_path: &Path, dir: &Path) -> Result<()> {
+    let spec = CliSpec::load(spec_path)?;
+    let package_dir = spec::cli_package_dir(dir)?;
+    let project_name = spec::binary_name(&package_dir)?;
+    let subcommands = spec_subcommands(&spec)?;
+    // Keep the hidden completions and man subcommands of projects generated with them
+    let completions = manifests_mention(&package_dir, |line| line.trim_start().starts_with("clap_complete"))?;
+
+    let mut writer = FileWriter::new(&package_dir, ConflictResolver::default());
+    if let Err(e) = generate_spec_cli(
+        &project_name,
+        &spec,
+        &subcommands,
+        completions,
+        &package_dir.join("src"),
+        &mut writer,
+    ) {
+        writer.journal.rollback()?;
+        return Err(e);
+    }
+
+    let clap_env_enabled = manifests_mention(&package_dir, |line| {
+        line.trim_start().starts_with("clap") && line.contains("\"env\"")
+    })?;
+    if spec.uses_env() && !clap_env_enabled {
+        println!(
+            "{} The spec reads environment variables; enable clap's env feature with: cargo add clap --features env",
+            "⚠".yellow()
+        );
+    }
+
+    println!(
+        "{} Regenerated {} from {}",
+        "✓".green(),
+        package_dir.join("src").join("cli.rs").display(),
+        spec_path.display()
+    );
+    Ok(())
+}
+
+/// Whether a line of the package's manifest, or its workspace's, matches `matches`
+fn manifests_mention(package_dir: &Path, matches: impl Fn(&str) -> bool) -> Result<bool> {
+    let mut manifests = vec![package_dir.join("Cargo.toml")];
+    if let Some(workspace) = Workspace::find_enclosing(package_dir)? {
+        manifests.push(workspace.manifest());
+    }
+
+    Ok(manifests
+        .iter()
+        .any(|manifest| fs::read_to_string(manifest).is_ok_and(|content| content.lines().any(&matches))))
+}
+
+/// Root virtual manifest plus `crates/<name>-cli` (the binary) and `crates/<name>-core` (the library)
+fn generate_workspace_crates(
+    project_name: &str,
+    target_dir: &Path,
+    author: &str,
+    config: &Config,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let crates_dir = target_dir.join("crates");
+    let cli_dir = crates_dir.join(format!("{}-cli", project_name));
+    let core_dir = crates_dir.join(format!("{}-core", project_name));
+    let cli_src_dir = cli_dir.join("src");
+    let core_src_dir = core_dir.join("src");
+    writer.create_dir_all(&cli_src_dir)?;
+    writer.create_dir_all(&core_src_dir)?;
+
+    generate_workspace_manifest(project_name, target_dir, author, config, writer)?;
+    generate_member_manifests(project_name, &cli_dir, &core_dir, config, writer)?;
+
+    // The binary crate gets today's package sources; git lives at the workspace root
+    generate_build_rs(&cli_dir, "../../.git", writer)?;
+    let cli_package = format!("{}-cli", project_name);
+    generate_sources(project_name, &cli_package, &cli_src_dir, config, writer)?;
+    generate_cli_tests(project_name, config.template.config_format, &cli_dir, writer)?;
+    if config.template.has_component(Component::Bench) {
+        generate_bench(
+            project_name,
+            config.template.config_format,
+            &cli_package,
+            &cli_dir,
+            writer,
+        )?;
+    }
+    if config.template.has_component(Component::Testing) {
+        generate_fuzz(
+            project_name,
+            config.template.config_format,
+            &cli_package,
+            &cli_dir,
+            writer,
+        )?;
+    }
+
+    generate_core_lib_rs(project_name, &core_src_dir, writer)?;
+
+    println!(
+        "{} Generated workspace crates {}-cli and {}-core",
+        "✓".green(),
+        project_name,
+        project_name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFormat;
+    use tempfile::TempDir;
+
+    fn create_test_config() -> Config {
+        Config::default()
+    }
+
+    #[test]
+    fn test_generate_project_creates_all_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-project";
+        let author = "Test Author <test@example.com>";
+        let config = create_test_config();
+
+        let result = generate_project(
+            project_name,
+            temp_dir.path(),
+            author,
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        // Check that all expected files were created
+        assert!(temp_dir.path().join("Cargo.toml").exists());
+        assert!(temp_dir.path().join("build.rs").exists());
+        assert!(temp_dir.path().join("src").exists());
+        assert!(temp_dir.path().join("src/main.rs").exists());
+        assert!(temp_dir.path().join("src/cli.rs").exists());
+        assert!(temp_dir.path().join("src/config.rs").exists());
+        assert!(temp_dir.path().join("src/config/tests.rs").exists());
+        assert!(temp_dir.path().join("tests/cli.rs").exists());
+        assert!(temp_dir.path().join(format!("{}.yml", project_name)).exists());
+        assert!(temp_dir.path().join(".github/workflows/ci.yml").exists());
+        assert!(temp_dir.path().join(".github/workflows/release.yml").exists());
+        assert!(temp_dir.path().join(".pre-commit-config.yaml").exists());
+    }
+
+    #[test]
+    fn test_generate_project_with_toml_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.config_format = ConfigFormat::Toml;
+
+        generate_project(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        assert!(temp_dir.path().join("tool.toml").exists());
+        assert!(!temp_dir.path().join("tool.yml").exists());
+        let config_rs = fs::read_to_string(temp_dir.path().join("src/config.rs")).unwrap();
+        assert!(config_rs.contains("toml::from_str(&content)"));
+        let tests_rs = fs::read_to_string(temp_dir.path().join("src/config/tests.rs")).unwrap();
+        assert!(tests_rs.contains("join(\"test.toml\")"));
+    }
+
+    #[test]
+    fn test_generate_project_with_different_authors() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-authors";
+        let config = create_test_config();
+
+        let authors = [
+            "Simple Name",
+            "Name With Spaces <email@example.com>",
+            "Unicode Äuthör <ünicöde@example.com>",
+        ];
+
+        for author in authors.iter() {
+            let project_dir = temp_dir.path().join(author.replace(['<', '>', '@', ' '], "_"));
+            let result = generate_project(
+                project_name,
+                &project_dir,
+                author,
+                &config,
+                false,
+                None,
+                &mut FileWriter::default(),
+            );
+            assert!(result.is_ok(), "Failed for author: {}", author);
+
+            let cargo_toml = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+            assert!(cargo_toml.contains(author));
+        }
+    }
+
+    #[test]
+    fn test_generate_project_with_special_characters_in_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config();
+        let author = "Test Author";
+
+        let project_names = ["simple", "with-dashes", "with_underscores", "with123numbers"];
+
+        for project_name in project_names.iter() {
+            let project_dir = temp_dir.path().join(project_name);
+            let result = generate_project(
+                project_name,
+                &project_dir,
+                author,
+                &config,
+                false,
+                None,
+                &mut FileWriter::default(),
+            );
+            assert!(result.is_ok(), "Failed for project name: {}", project_name);
+
+            let cargo_toml = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+            assert!(cargo_toml.contains(&format!("name = \"{}\"", project_name)));
+
+            let main_rs = fs::read_to_string(project_dir.join("src/main.rs")).unwrap();
+            assert!(main_rs.contains(project_name));
+
+            let cli_rs = fs::read_to_string(project_dir.join("src/cli.rs")).unwrap();
+            assert!(cli_rs.contains(&format!("name = \"{}\"", project_name)));
+
+            let sample_config = project_dir.join(format!("{}.yml", project_name));
+            assert!(sample_config.exists());
+        }
+    }
+
+    fn writer_with(root: &Path, default: ConflictPolicy, rules: &[conflict::ConflictRule]) -> FileWriter {
+        FileWriter::new(root, ConflictResolver::new(default, rules).unwrap())
+    }
+
+    #[test]
+    fn test_file_writer_skip_keeps_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("clippy.toml");
+        fs::write(&file, "existing").unwrap();
+
+        let mut writer = writer_with(temp_dir.path(), ConflictPolicy::Skip, &[]);
+        writer.write_file(&file, "generated").unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "existing");
+    }
+
+    #[test]
+    fn test_file_writer_backup_writes_orig_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("clippy.toml");
+        fs::write(&file, "existing").unwrap();
+
+        let mut writer = writer_with(temp_dir.path(), ConflictPolicy::Backup, &[]);
+        writer.write_file(&file, "generated").unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "generated");
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("clippy.toml.orig")).unwrap(),
+            "existing"
+        );
+
+        // Rolling back removes the backup and restores the original
+        writer.journal.rollback().unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "existing");
+        assert!(!temp_dir.path().join("clippy.toml.orig").exists());
+    }
+
+    #[test]
+    fn test_file_writer_backup_keeps_earlier_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("clippy.toml");
+        fs::write(&file, "current").unwrap();
+        fs::write(temp_dir.path().join("clippy.toml.orig"), "from last run").unwrap();
+
+        let mut writer = writer_with(temp_dir.path(), ConflictPolicy::Backup, &[]);
+        writer.write_file(&file, "generated").unwrap();
+
+        let read = |name: &str| fs::read_to_string(temp_dir.path().join(name)).unwrap();
+        assert_eq!(read("clippy.toml"), "generated");
+        assert_eq!(read("clippy.toml.orig"), "from last run");
+        assert_eq!(read("clippy.toml.orig.1"), "current");
+    }
+
+    #[test]
+    fn test_file_writer_merge_adds_missing_toml_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("Cargo.toml");
+        fs::write(&file, "[package]\nname = \"mine\"\n").unwrap();
+
+        let mut writer = writer_with(temp_dir.path(), ConflictPolicy::Merge, &[]);
+        writer
+            .write_file(&file, "[package]\nname = \"generated\"\nedition = \"2024\"\n")
+            .unwrap();
+
+        let content = fs::read_to_string(&file).unwrap();
+        assert!(content.contains("name = \"mine\""));
+        assert!(content.contains("edition = \"2024\""));
+    }
+
+    #[test]
+    fn test_file_writer_rules_match_relative_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let workflows_dir = temp_dir.path().join(".github").join("workflows");
+        fs::create_dir_all(&workflows_dir).unwrap();
+        fs::write(workflows_dir.join("ci.yml"), "existing").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "existing").unwrap();
+
+        let rules = [
+            conflict::ConflictRule {
+                path: "README.md".to_string(),
+                policy: ConflictPolicy::Skip,
+            },
+            conflict::ConflictRule {
+                path: ".github/workflows/*".to_string(),
+                policy: ConflictPolicy::Overwrite,
+            },
+        ];
+        let mut writer = writer_with(temp_dir.path(), ConflictPolicy::Backup, &rules);
+        writer.write_file(&workflows_dir.join("ci.yml"), "generated").unwrap();
+        writer
+            .write_file(&temp_dir.path().join("README.md"), "generated")
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(workflows_dir.join("ci.yml")).unwrap(), "generated");
+        assert!(!workflows_dir.join("ci.yml.orig").exists());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("README.md")).unwrap(),
+            "existing"
+        );
+    }
+
+    #[test]
+    fn test_generate_project_creates_src_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-src";
+        let author = "Test Author";
+        let config = create_test_config();
+
+        // Ensure src directory doesn't exist initially
+        assert!(!temp_dir.path().join("src").exists());
+
+        let result = generate_project(
+            project_name,
+            temp_dir.path(),
+            author,
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        // Verify src directory was created
+        assert!(temp_dir.path().join("src").exists());
+        assert!(temp_dir.path().join("src").is_dir());
+    }
+
+    #[test]
+    fn test_generate_project_joining_workspace_watches_root_git() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        let member_dir = temp_dir.path().join("crates").join("tool");
+        let workspace = Workspace::find_enclosing(&member_dir).unwrap().unwrap();
+
+        generate_project(
+            "tool",
+            &member_dir,
+            "Test Author <test@example.com>",
+            &create_test_config(),
+            false,
+            Some(&workspace),
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let build_rs = fs::read_to_string(member_dir.join("build.rs")).unwrap();
+        assert!(build_rs.contains("cargo:rerun-if-changed=../../.git/HEAD"));
+        assert!(build_rs.contains("cargo:rerun-if-changed=../../.git/refs/"));
+    }
+
+    #[test]
+    fn test_generate_project_workspace_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.kind = ProjectKind::Workspace;
+
+        generate_project(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            true,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let root = temp_dir.path();
+        assert!(root.join("Cargo.toml").exists());
+        assert!(!root.join("src").exists());
+        assert!(!root.join("build.rs").exists());
+        assert!(root.join("crates/tool-cli/Cargo.toml").exists());
+        assert!(root.join("crates/tool-cli/build.rs").exists());
+        assert!(root.join("crates/tool-cli/src/main.rs").exists());
+        assert!(root.join("crates/tool-cli/tests/cli.rs").exists());
+        assert!(root.join("crates/tool-cli/src/cli.rs").exists());
+        assert!(root.join("crates/tool-cli/src/config.rs").exists());
+        assert!(root.join("crates/tool-cli/src/config/tests.rs").exists());
+        assert!(root.join("crates/tool-core/Cargo.toml").exists());
+        assert!(root.join("crates/tool-core/src/lib.rs").exists());
+        assert!(root.join("tool.yml").exists());
+        assert!(root.join("clippy.toml").exists());
+
+        let ci_yml = fs::read_to_string(root.join(".github/workflows/ci.yml")).unwrap();
+        assert!(ci_yml.contains("--workspace"));
+        let otto_yml = fs::read_to_string(root.join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("--workspace"));
+    }
+
+    #[test]
+    fn test_generate_project_with_bench_component() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.kind = ProjectKind::Workspace;
+        config.template.create_lib = true;
+        config.template.components = vec![Component::Bench];
+
+        generate_project(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            true,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let root = temp_dir.path();
+        let bench_rs = fs::read_to_string(root.join("crates/tool-cli/benches/tool.rs")).unwrap();
+        assert!(bench_rs.contains("use tool_cli::Config;"));
+        let cli_toml = fs::read_to_string(root.join("crates/tool-cli/Cargo.toml")).unwrap();
+        assert!(cli_toml.contains("[[bench]]\nname = \"tool\"\nharness = false"));
+        let otto_yml = fs::read_to_string(root.join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("cargo bench --workspace"));
+    }
+
+    #[test]
+    fn test_generate_project_with_testing_component() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.create_lib = true;
+        config.template.components = vec![Component::Testing];
+
+        generate_project(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            true,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let root = temp_dir.path();
+        assert!(root.join("src/config/proptests.rs").exists());
+        let config_rs = fs::read_to_string(root.join("src/config.rs")).unwrap();
+        assert!(config_rs.contains("mod proptests;"));
+        let target = fs::read_to_string(root.join("fuzz/fuzz_targets/parse_config.rs")).unwrap();
+        assert!(target.contains("tool::Config"));
+        let cargo_toml = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("proptest = \"*\""));
+        let otto_yml = fs::read_to_string(root.join(".otto.yml")).unwrap();
+        assert!(otto_yml.contains("cargo +nightly fuzz run parse_config"));
+    }
+
+    #[test]
+    fn test_generate_project_with_lib() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.create_lib = true;
+
+        generate_project(
+            "lib-tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        assert!(temp_dir.path().join("src/lib.rs").exists());
+        let main_rs = fs::read_to_string(temp_dir.path().join("src/main.rs")).unwrap();
+        assert!(main_rs.contains("lib_tool::run(&cli, &config)"));
+    }
+
+    #[test]
+    fn test_generate_project_workspace_with_lib_uses_cli_crate() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.kind = ProjectKind::Workspace;
+        config.template.create_lib = true;
+
+        generate_project(
+            "tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            true,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        assert!(temp_dir.path().join("crates/tool-cli/src/lib.rs").exists());
+        let main_rs = fs::read_to_string(temp_dir.path().join("crates/tool-cli/src/main.rs")).unwrap();
+        assert!(main_rs.contains("use tool_cli::{Cli, Config};"));
+    }
+
+    #[test]
+    fn test_generate_project_with_subcommands() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.cli.subcommands = vec!["init".to_string(), "sync-all".to_string()];
+
+        generate_project(
+            "sub-tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let src_dir = temp_dir.path().join("src");
+        assert!(src_dir.join("commands/mod.rs").exists());
+        assert!(src_dir.join("commands/init.rs").exists());
+        assert!(src_dir.join("commands/sync_all.rs").exists());
+
+        let cli_rs = fs::read_to_string(src_dir.join("cli.rs")).unwrap();
+        assert!(cli_rs.contains("use clap::{Parser, Subcommand};"));
+        assert!(cli_rs.contains("pub command: Command,"));
+        assert!(cli_rs.contains("SyncAll(crate::commands::sync_all::SyncAllArgs),"));
+
+        let main_rs = fs::read_to_string(src_dir.join("main.rs")).unwrap();
+        assert!(main_rs.contains("mod commands;"));
+        assert!(main_rs.contains("commands::dispatch(&cli.command, config)"));
+
+        let mod_rs = fs::read_to_string(src_dir.join("commands/mod.rs")).unwrap();
+        assert!(mod_rs.contains("Command::SyncAll(args) => sync_all::run(args, config),"));
+    }
+
+    #[test]
+    fn test_generate_project_rejects_invalid_subcommands() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.cli.subcommands = vec!["Bad Name".to_string()];
+
+        let result = generate_project(
+            "sub-tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        );
+
+        assert!(result.unwrap_err().to_string().contains("Invalid subcommand name"));
+    }
+
+    #[test]
+    fn test_generate_project_from_cli_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec_path = temp_dir.path().join("tool-spec.yml");
+        fs::write(
+            &spec_path,
+            "commands:\n  - name: add\n    args:\n      - name: item\n        kind: positional\n",
+        )
+        .unwrap();
+        let project_dir = temp_dir.path().join("spec-tool");
+        let mut config = create_test_config();
+        config.template.cli.spec = Some(spec_path);
+
+        generate_project(
+            "spec-tool",
+            &project_dir,
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        assert!(project_dir.join(SPEC_FILE).exists());
+        assert!(project_dir.join("src/cli/tests.rs").exists());
+        assert!(project_dir.join("src/commands/add.rs").exists());
+        let cli_rs = fs::read_to_string(project_dir.join("src/cli.rs")).unwrap();
+        assert!(cli_rs.contains("pub struct AddArgs {\n    pub item: String,\n}"));
+    }
+
+    #[test]
+    fn test_generate_project_rejects_spec_with_subcommands() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config();
+        config.template.cli.spec = Some(temp_dir.path().join(SPEC_FILE));
+        config.template.cli.subcommands = vec!["init".to_string()];
+        fs::write(temp_dir.path().join(SPEC_FILE), "commands:\n  - name: add\n").unwrap();
+
+        let result = generate_project(
+            "spec-tool",
+            &temp_dir.path().join("spec-tool"),
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        );
+
+        assert!(result.unwrap_err().to_string().contains("not both"));
+    }
+
+    #[test]
+    fn test_regenerate_cli_keeps_handlers() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec_path = temp_dir.path().join(SPEC_FILE);
+        fs::write(&spec_path, "commands:\n  - name: add\n").unwrap();
+        let mut config = create_test_config();
+        config.template.cli.spec = Some(spec_path.clone());
+        generate_project(
+            "regen-tool",
+            temp_dir.path(),
+            "Test Author",
+            &config,
+            false,
+            None,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+        let handler = temp_dir.path().join("src/commands/add.rs");
+        fs::write(&handler, "// implemented").unwrap();
+
+        fs::write(&spec_path, "commands:\n  - name: add\n  - name: remove\n").unwrap();
+        regenerate_cli(&spec_path, temp_dir.path()).unwrap();
+
+        assert_eq!(fs::read_to_string(&handler).unwrap(), "// implemented");
+        assert!(temp_dir.path().join("src/commands/remove.rs").exists());
+        let cli_rs = fs::read_to_string(temp_dir.path().join("src/cli.rs")).unwrap();
+        assert!(cli_rs.contains("Remove(RemoveArgs),"));
+        assert!(cli_rs.contains("name = \"regen-tool\""));
+    }
+}
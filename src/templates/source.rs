@@ -0,0 +1,1469 @@
+use super::FileWriter;
+use super::commands::{Subcommand, builtin_arms, builtin_variants, command_enum, dispatch};
+use super::logging::setup_logging_fn;
+use crate::config::{ConfigFormat, LoggingBackend};
+use eyre::Result;
+use std::path::Path;
+
+/// Application logic shared by `run_application` in main.rs and `run` in lib.rs
+const RUN_BODY: &str = r#"{{BUILTIN_DISPATCH}}
+    info!("Starting application");
+
+    // Load and display configuration
+    println!("{}", "✓ Configuration loaded successfully".green());
+    if cli.verbose > 0 {
+        println!("{}", "🔍 Verbose mode enabled".yellow());
+    }
+    if config.debug {
+        println!("{}", "🔍 Debug mode enabled".yellow());
+    }
+
+    // Demonstrate colored output
+    println!("{} Hello from {}!", "🎉".green(), "{{PROJECT}}".cyan());
+    println!("{} Author: {}", "👤".blue(), config.name);
+    println!("{} Age: {}", "📅".blue(), config.age);
+
+    // Log some information
+    info!("Application executed successfully");
+
+    Ok(())"#;
+
+/// --config/--set fields the generated `Cli` starts with
+pub(super) const CONFIG_ARGS: &str = r#"    /// Path to config file
+    #[arg(short, long, help = "Path to config file")]
+    pub config: Option<PathBuf>,
+
+    /// Override a config key, e.g. --set log.keep=3
+    #[arg(
+        long,
+        value_name = "KEY=VALUE",
+        help = "Override a config key, e.g. --set log.keep=3 (repeatable)"
+    )]
+    pub set: Vec<String>,"#;
+
+/// -v/-q fields of the generated `Cli`, following `--config` and `--set`
+pub(super) const VERBOSITY_ARGS: &str = r#"    /// Log more (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, action = clap::ArgAction::Count, help = "Log more (-v info, -vv debug, -vvv trace)")]
+    pub verbose: u8,
+
+    /// Only log errors
+    #[arg(short, long, conflicts_with = "verbose", help = "Only log errors")]
+    pub quiet: bool,"#;
+
+/// `Cli::log_level`, turning -v/-q into the level setup_logging() starts at
+pub(super) const LOG_LEVEL_IMPL: &str = r#"impl Cli {
+    /// Log level chosen with -v/-q; warnings and errors by default
+    pub fn log_level(&self) -> log::LevelFilter {
+        if self.quiet {
+            return log::LevelFilter::Error;
+        }
+        match self.verbose {
+            0 => log::LevelFilter::Warn,
+            1 => log::LevelFilter::Info,
+            2 => log::LevelFilter::Debug,
+            _ => log::LevelFilter::Trace,
+        }
+    }
+}
+"#;
+
+/// `Command` variant for the `config` subcommands, which every generated CLI has
+pub(super) const CONFIG_VARIANT: &str = r#"    /// Inspect or create the config file
+    #[command(subcommand)]
+    Config(ConfigCommand),
+"#;
+
+/// Hidden `Command` variants printing completions and writing man pages, added with template.create-completions
+pub(super) const COMPLETIONS_VARIANTS: &str = r#"    /// Print shell completions
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+    /// Write man pages for the CLI and its subcommands
+    #[command(hide = true)]
+    Man {
+        /// Directory to write the pages to
+        dir: PathBuf,
+    },
+"#;
+
+/// Functions behind the hidden `completions` and `man` subcommands
+pub(super) const COMPLETIONS_FNS: &str = r#"
+/// Print completions for `shell` to stdout
+pub fn print_completions(shell: clap_complete::Shell) {
+    let mut command = <Cli as clap::CommandFactory>::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+}
+
+/// Write a man page for the CLI and each of its subcommands to `dir`
+pub fn write_man_pages(dir: &std::path::Path) -> eyre::Result<()> {
+    use eyre::Context;
+
+    std::fs::create_dir_all(dir).context(format!("Failed to create {}", dir.display()))?;
+    clap_mangen::generate_to(<Cli as clap::CommandFactory>::command(), dir)
+        .context("Failed to write man pages")
+}
+"#;
+
+/// The `config` subcommands, handled by `config::run_command`
+pub(super) const CONFIG_COMMAND: &str = r#"
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// List the config files searched, in order, and which one was loaded
+    Path,
+    /// Write the default config to the primary location
+    Init {
+        /// Replace an existing config file
+        #[arg(short, long, help = "Replace an existing config file")]
+        force: bool,
+    },
+    /// Print the effective config, with environment and --set overrides applied
+    Show,
+    /// Check that a config file parses
+    Validate {
+        /// Config file to check
+        file: PathBuf,
+    },
+}
+"#;
+
+/// Prefix of the environment variables overriding config keys: `my-tool` reads `MY_TOOL_<KEY>`
+pub(super) fn env_prefix(project_name: &str) -> String {
+    project_name.to_uppercase().replace('-', "_")
+}
+
+/// `after_help` of the generated `Cli`, spelling out which config source wins
+pub(super) fn config_precedence(project_name: &str) -> String {
+    format!(
+        "Config precedence, lowest to highest: defaults, the config file, {}_<KEY> environment variables \
+         (e.g. {}_LOG_KEEP for log.keep), --set KEY=VALUE",
+        env_prefix(project_name),
+        env_prefix(project_name)
+    )
+}
+
+/// Body of `run_application`/`run`: the demo output, or dispatch to the subcommand handlers when there are any
+fn run_body(subcommands: &[Subcommand], completions: bool) -> String {
+    if subcommands.is_empty() {
+        // The built-in subcommands are the only ones, so they're handled here rather than in commands/mod.rs
+        let arms: String = builtin_arms(completions)
+            .lines()
+            .map(|line| format!("    {}\n", line))
+            .collect();
+        let builtin_dispatch = format!(
+            r#"    // Built-in subcommands report on the configuration or the CLI instead of running the application
+    if let Some(command) = &cli.command {{
+        use crate::cli::Command;
+
+        return match command {{
+{}        }};
+    }}
+"#,
+            arms
+        );
+        return RUN_BODY.replace("{{BUILTIN_DISPATCH}}", &builtin_dispatch);
+    }
+
+    format!(
+        r#"    info!("Starting application");
+
+    if cli.verbose > 0 {{
+        println!("{{}}", "🔍 Verbose mode enabled".yellow());
+    }}
+    if config.debug {{
+        println!("{{}}", "🔍 Debug mode enabled".yellow());
+    }}
+
+{}"#,
+        dispatch()
+    )
+}
+
+/// main.rs; given the package's `lib` crate it only parses arguments, sets up logging and calls the library's `run`
+pub(super) fn generate_main_rs(
+    project_name: &str,
+    src_dir: &Path,
+    lib: Option<&str>,
+    subcommands: &[Subcommand],
+    logging: LoggingBackend,
+    completions: bool,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let (imports, run_fn, run_call) = if let Some(lib) = lib {
+        let lib = lib.replace('-', "_");
+        (
+            format!(
+                "use eyre::{{Context, Result}};\nuse log::{{info, warn}};\nuse std::fs;\nuse std::path::PathBuf;\n\nuse {lib}::config::{{LogConfig, RotatingFile}};\nuse {lib}::{{Cli, Config}};\n"
+            ),
+            String::new(),
+            format!("{}::run", lib),
+        )
+    } else {
+        let modules = if subcommands.is_empty() {
+            "mod cli;\nmod config;\n\nuse cli::Cli;\n"
+        } else {
+            "mod cli;\nmod commands;\nmod config;\n\nuse cli::Cli;\n"
+        };
+        (
+            format!(
+                "use colored::*;\nuse eyre::{{Context, Result}};\nuse log::{{info, warn}};\nuse std::fs;\nuse std::path::PathBuf;\n\n{}use config::{{Config, LogConfig, RotatingFile}};\n",
+                modules
+            ),
+            format!(
+                "fn run_application(cli: &Cli, config: &Config) -> Result<()> {{\n{}\n}}\n\n",
+                run_body(subcommands, completions)
+            ),
+            "run_application".to_string(),
+        )
+    };
+
+    // fern already copies log records to stderr; the other backends only write the log file
+    let report_warning = match logging {
+        LoggingBackend::Fern => "        warn!(\"{}\", warning);",
+        LoggingBackend::EnvLogger | LoggingBackend::Tracing => {
+            "        warn!(\"{}\", warning);\n        eprintln!(\"Warning: {}\", warning);"
+        }
+    };
+
+    let main_rs = r#"#![deny(clippy::unwrap_used)]
+#![deny(dead_code)]
+#![deny(unused_variables)]
+
+use clap::Parser;
+{{IMPORTS}}
+{{SETUP_LOGGING}}
+
+{{RUN_FN}}fn main() -> Result<()> {
+    // Parse CLI arguments
+    let cli = Cli::parse();
+
+    // Load configuration first, it decides how the log file rotates
+    let (config, warnings) = Config::load(cli.config.as_ref())
+        .and_then(|(config, warnings)| Ok((config.with_overrides(&cli.set)?, warnings)))
+        .context("Failed to load configuration")?;
+
+    // Setup logging at the level chosen with -v/-q
+    setup_logging(cli.log_level(), &config.log).context("Failed to setup logging")?;
+
+    // Config files skipped while loading are only reported now that logging is set up
+    for warning in &warnings {
+{{REPORT_WARNING}}
+    }
+
+    info!("Starting with config from: {:?}", cli.config);
+
+    // Run the main application logic
+    {{RUN_CALL}}(&cli, &config).context("Application failed")?;
+
+    Ok(())
+}
+"#
+    .replace("{{IMPORTS}}", &imports)
+    .replace("{{SETUP_LOGGING}}", &setup_logging_fn(logging, project_name))
+    .replace("{{RUN_FN}}", &run_fn)
+    .replace("{{RUN_CALL}}", &run_call)
+    .replace("{{REPORT_WARNING}}", report_warning)
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&src_dir.join("main.rs"), &main_rs)?;
+
+    Ok(())
+}
+
+/// lib.rs exposing the cli and config modules and `run`, so the application can be reused and tested as a library
+pub(super) fn generate_lib_rs(
+    project_name: &str,
+    src_dir: &Path,
+    subcommands: &[Subcommand],
+    completions: bool,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let (modules, test_args) = match subcommands.first() {
+        None => (
+            "pub mod cli;\npub mod config;\n\npub use cli::Cli;".to_string(),
+            String::new(),
+        ),
+        Some(first) => (
+            "pub mod cli;\npub mod commands;\npub mod config;\n\npub use cli::{Cli, Command};".to_string(),
+            std::iter::once(&first.name)
+                .chain(&first.sample_args)
+                .map(|arg| format!(", {:?}", arg))
+                .collect(),
+        ),
+    };
+
+    let lib_rs = r#"#![deny(clippy::unwrap_used)]
+
+use colored::*;
+use eyre::Result;
+use log::info;
+
+{{MODULES}}
+pub use config::Config;
+
+/// Run the application with parsed arguments and loaded configuration
+pub fn run(cli: &Cli, config: &Config) -> Result<()> {
+{{RUN_BODY}}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_run_with_default_config() {
+        let cli = Cli::parse_from(["{{PROJECT}}"{{TEST_ARGS}}]);
+        assert!(run(&cli, &Config::default()).is_ok());
+    }
+}
+"#
+    .replace("{{MODULES}}", &modules)
+    .replace("{{TEST_ARGS}}", &test_args)
+    .replace("{{RUN_BODY}}", &run_body(subcommands, completions))
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&src_dir.join("lib.rs"), &lib_rs)?;
+
+    Ok(())
+}
+
+pub(super) fn generate_cli_rs(
+    project_name: &str,
+    subcommands: &[Subcommand],
+    completions: bool,
+    src_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    // Without subcommands of its own the CLI still takes `config`, so the command is optional
+    let (command_field, command_enum) = if subcommands.is_empty() {
+        (
+            "\n\n    #[command(subcommand)]\n    pub command: Option<Command>,",
+            format!(
+                "\n#[derive(Subcommand)]\npub enum Command {{\n{}}}\n",
+                builtin_variants(completions)
+            ),
+        )
+    } else {
+        (
+            "\n\n    #[command(subcommand)]\n    pub command: Command,",
+            command_enum(subcommands, completions),
+        )
+    };
+    let completions_fns = if completions { COMPLETIONS_FNS } else { "" };
+
+    let cli_rs = r#"use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(
+    name = "{{PROJECT}}",
+    about = "A CLI application generated by rust-scaffold",
+    version = env!("GIT_DESCRIBE"),
+    after_help = "{{CONFIG_PRECEDENCE}}",
+)]
+pub struct Cli {
+{{CONFIG_ARGS}}
+
+{{VERBOSITY_ARGS}}{{COMMAND_FIELD}}
+}
+
+{{LOG_LEVEL_IMPL}}{{COMMAND_ENUM}}{{CONFIG_COMMAND}}{{COMPLETIONS_FNS}}"#
+        .replace("{{CONFIG_ARGS}}", CONFIG_ARGS)
+        .replace("{{CONFIG_PRECEDENCE}}", &config_precedence(project_name))
+        .replace("{{VERBOSITY_ARGS}}", VERBOSITY_ARGS)
+        .replace("{{LOG_LEVEL_IMPL}}", LOG_LEVEL_IMPL)
+        .replace("{{COMMAND_FIELD}}", command_field)
+        .replace("{{COMMAND_ENUM}}", &command_enum)
+        .replace("{{CONFIG_COMMAND}}", CONFIG_COMMAND)
+        .replace("{{COMPLETIONS_FNS}}", completions_fns)
+        .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&src_dir.join("cli.rs"), &cli_rs)?;
+
+    Ok(())
+}
+
+/// Display name, crate and serializing function of `format` in the generated config module
+pub(super) fn format_parser(format: ConfigFormat) -> (&'static str, &'static str, &'static str) {
+    match format {
+        ConfigFormat::Yaml => ("YAML", "serde_yaml", "to_string"),
+        ConfigFormat::Toml => ("TOML", "toml", "to_string_pretty"),
+        ConfigFormat::Json => ("JSON", "serde_json", "to_string_pretty"),
+    }
+}
+
+pub(super) fn generate_config_rs(
+    project_name: &str,
+    format: ConfigFormat,
+    proptests: bool,
+    src_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let (format_name, parser, serialize) = format_parser(format);
+    let proptests_mod = if proptests {
+        "\n\n#[cfg(test)]\nmod proptests;"
+    } else {
+        ""
+    };
+    let config_rs = r#"use crate::cli::ConfigCommand;
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the config directory and file, matching the binary name
+const APP_NAME: &str = "{{PROJECT}}";
+
+/// Extension of the config files `load` looks for
+const CONFIG_EXTENSION: &str = "{{EXTENSION}}";
+
+/// Prefix of the environment variables overriding config keys, e.g. {{ENV_PREFIX}}_LOG_KEEP for log.keep
+const ENV_PREFIX: &str = "{{ENV_PREFIX}}";
+
+/// Keys accepted by --set and, upper-cased under ENV_PREFIX, as environment variables
+const KEYS: &[&str] = &[
+    "name",
+    "age",
+    "debug",
+    "log.rotation",
+    "log.max-size-mb",
+    "log.keep",
+];
+
+/// Written by `config init`; the same values as `Config::default()`
+const SAMPLE_CONFIG: &str = {{SAMPLE_CONFIG}};
+
+// {{FORMAT}} keys use kebab-case (e.g., my-field); serde translates to snake_case in Rust.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    pub name: String,
+    pub age: u32,
+    pub debug: bool,
+    pub log: LogConfig,
+    /// File the values were read from, if any
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            name: "John Doe".to_string(),
+            age: 30,
+            debug: false,
+            log: LogConfig::default(),
+            source: None,
+        }
+    }
+}
+
+/// How the log file is rotated and how many old files are kept
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LogConfig {
+    pub rotation: Rotation,
+    /// Size at which `rotation: size` starts a new file
+    pub max_size_mb: u64,
+    /// Rotated files kept as <project>.log.1 (newest) to <project>.log.<keep>
+    pub keep: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            rotation: Rotation::Size,
+            max_size_mb: 10,
+            keep: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rotation {
+    /// Once the file reaches max-size-mb
+    Size,
+    /// On the first write of each UTC day
+    Daily,
+    /// Never; the file grows without bound
+    Never,
+}
+
+impl std::str::FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "size" => Ok(Self::Size),
+            "daily" => Ok(Self::Daily),
+            "never" => Ok(Self::Never),
+            _ => Err("expected size, daily or never".to_string()),
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration with fallback chain
+    ///
+    /// Search path files that fail to load are skipped; the returned warnings say why, so they can
+    /// be reported once logging is set up.
+    pub fn load(config_path: Option<&PathBuf>) -> Result<(Self, Vec<String>)> {
+        // If explicit config path provided, try to load it
+        if let Some(path) = config_path {
+            return Self::load_from_file(path)
+                .map(|config| (config, Vec::new()))
+                .context(format!("Failed to load config from {}", path.display()));
+        }
+
+        // Otherwise the first search path that loads wins
+        let mut warnings = Vec::new();
+        for path in Self::search_paths() {
+            if path.exists() {
+                match Self::load_from_file(&path) {
+                    Ok(config) => return Ok((config, warnings)),
+                    Err(e) => warnings.push(format!(
+                        "Failed to load config from {}: {}",
+                        path.display(),
+                        e
+                    )),
+                }
+            }
+        }
+
+        // No config file found, use defaults
+        Ok((Self::default(), warnings))
+    }
+
+    /// Config files `load` tries without --config, in order
+    pub fn search_paths() -> Vec<PathBuf> {
+        let file_name = format!("{}.{}", APP_NAME, CONFIG_EXTENSION);
+        let mut paths = Vec::new();
+
+        // Primary location: <platform config dir>/<project>/<project>.{{EXTENSION}}
+        // Linux: ~/.config (or $XDG_CONFIG_HOME), macOS: ~/Library/Application Support
+        if let Some(config_dir) = dirs::config_dir() {
+            paths.push(config_dir.join(APP_NAME).join(&file_name));
+        }
+
+        // Fallback location: ./<project>.{{EXTENSION}}
+        paths.push(PathBuf::from(file_name));
+        paths
+    }
+
+    fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path).context("Failed to read config file")?;
+
+        let mut config: Self = {{PARSER}}::from_str(&content).context("Failed to parse config")?;
+        config.source = Some(path.as_ref().to_path_buf());
+
+        log::info!("Loaded config from: {}", path.as_ref().display());
+        Ok(config)
+    }
+
+    /// Layer {{ENV_PREFIX}}_<KEY> environment variables, then `--set key=value` pairs, over the loaded values
+    pub fn with_overrides(mut self, sets: &[String]) -> Result<Self> {
+        self.apply_env(std::env::vars())?;
+        for set in sets {
+            let (key, value) = set
+                .split_once('=')
+                .ok_or_else(|| eyre::eyre!("Expected --set key=value, got '{}'", set))?;
+            self.set(key.trim(), value)
+                .context(format!("Invalid --set {}", set))?;
+        }
+        Ok(self)
+    }
+
+    fn apply_env(&mut self, vars: impl IntoIterator<Item = (String, String)>) -> Result<()> {
+        let vars: Vec<(String, String)> = vars.into_iter().collect();
+        for key in KEYS {
+            let name = env_var_name(key);
+            if let Some((_, value)) = vars.iter().find(|(var, _)| *var == name) {
+                self.set(key, value).context(format!("Invalid {}", name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set one key from its string form; nested keys are dotted, e.g. log.keep
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "name" => self.name = value.to_string(),
+            "age" => self.age = parse_value(key, value)?,
+            "debug" => self.debug = parse_value(key, value)?,
+            "log.rotation" => self.log.rotation = parse_value(key, value)?,
+            "log.max-size-mb" => self.log.max_size_mb = parse_value(key, value)?,
+            "log.keep" => self.log.keep = parse_value(key, value)?,
+            _ => {
+                return Err(eyre::eyre!(
+                    "Unknown config key '{}' (expected one of: {})",
+                    key,
+                    KEYS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_value<T>(key: &str, value: &str) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| eyre::eyre!("Invalid value '{}' for {}: {}", value, key, e))
+}
+
+/// Handle `{{PROJECT}} config <command>`
+pub fn run_command(command: &ConfigCommand, config: &Config) -> Result<()> {
+    match command {
+        ConfigCommand::Path => {
+            let search_paths = Config::search_paths();
+            if let Some(source) = config
+                .source
+                .as_ref()
+                .filter(|source| !search_paths.contains(source))
+            {
+                println!("{} (--config, loaded)", source.display());
+            }
+            for path in &search_paths {
+                let status = if config.source.as_ref() == Some(path) {
+                    "loaded"
+                } else if path.exists() {
+                    "found"
+                } else {
+                    "missing"
+                };
+                println!("{} ({})", path.display(), status);
+            }
+            println!(
+                "Then {}_<KEY> environment variables and --set KEY=VALUE override single keys",
+                ENV_PREFIX
+            );
+        }
+        ConfigCommand::Init { force } => {
+            let search_paths = Config::search_paths();
+            let path = &search_paths[0];
+            if path.exists() && !force {
+                return Err(eyre::eyre!(
+                    "{} already exists; pass --force to replace it",
+                    path.display()
+                ));
+            }
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                fs::create_dir_all(parent)
+                    .context(format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(path, SAMPLE_CONFIG)
+                .context(format!("Failed to write {}", path.display()))?;
+            println!("Wrote {}", path.display());
+        }
+        ConfigCommand::Show => {
+            let shown = {{PARSER}}::{{SERIALIZE}}(config).context("Failed to format config")?;
+            println!("{}", shown.trim_end());
+        }
+        ConfigCommand::Validate { file } => {
+            Config::load_from_file(file)
+                .context(format!("{} is not a valid config", file.display()))?;
+            println!("{} is valid", file.display());
+        }
+    }
+    Ok(())
+}
+
+/// `log.max-size-mb` -> `<ENV_PREFIX>_LOG_MAX_SIZE_MB`
+fn env_var_name(key: &str) -> String {
+    let key = key.to_uppercase().replace(['-', '.'], "_");
+    format!("{}_{}", ENV_PREFIX, key)
+}
+
+/// Log file writer that rotates according to a `LogConfig`, shifting older files up by one
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    day: u64,
+    rotation: Rotation,
+    max_size: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    /// Open `path` for appending, continuing the size and day of an existing file
+    pub fn open(path: &Path, config: &LogConfig) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .context(format!("Failed to open log file {}", path.display()))?;
+        let metadata = file
+            .metadata()
+            .context("Failed to read log file metadata")?;
+        let day = metadata
+            .modified()
+            .map(day_of)
+            .unwrap_or_else(|_| day_of(SystemTime::now()));
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            size: metadata.len(),
+            day,
+            rotation: config.rotation,
+            max_size: config.max_size_mb.saturating_mul(1024 * 1024),
+            keep: config.keep,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..self.keep).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated(index + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// `<project>.log.<index>`
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let today = day_of(SystemTime::now());
+        let due = match self.rotation {
+            Rotation::Size => self.size > 0 && self.size + buf.len() as u64 > self.max_size,
+            Rotation::Daily => today != self.day,
+            Rotation::Never => false,
+        };
+        if due {
+            self.rotate()?;
+        }
+        self.day = today;
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Days since the Unix epoch, in UTC
+fn day_of(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests;{{PROPTESTS_MOD}}
+"#
+    .replace("{{PROPTESTS_MOD}}", proptests_mod)
+    .replace("{{FORMAT}}", format_name)
+    .replace(
+        "{{SAMPLE_CONFIG}}",
+        &format!("r##\"{}\"##", sample_config(project_name, format)),
+    )
+    .replace("{{PARSER}}", parser)
+    .replace("{{SERIALIZE}}", serialize)
+    .replace("{{EXTENSION}}", format.extension())
+    .replace("{{ENV_PREFIX}}", &env_prefix(project_name))
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&src_dir.join("config.rs"), &config_rs)?;
+
+    Ok(())
+}
+
+pub(super) fn generate_config_tests_rs(format: ConfigFormat, src_dir: &Path, writer: &mut FileWriter) -> Result<()> {
+    // Fixture files as Rust string literals: name/age/debug, then a partial log section
+    let (explicit_fixture, log_fixture) = match format {
+        ConfigFormat::Yaml => (
+            r#""name: Test User\nage: 42\ndebug: true""#,
+            r#""log:\n  rotation: daily\n  keep: 2""#,
+        ),
+        ConfigFormat::Toml => (
+            r#""name = \"Test User\"\nage = 42\ndebug = true""#,
+            r#""[log]\nrotation = \"daily\"\nkeep = 2""#,
+        ),
+        ConfigFormat::Json => (
+            r##"r#"{"name": "Test User", "age": 42, "debug": true}"#"##,
+            r##"r#"{"log": {"rotation": "daily", "keep": 2}}"#"##,
+        ),
+    };
+
+    let tests_dir = src_dir.join("config");
+    writer.create_dir_all(&tests_dir)?;
+
+    let tests_rs = r#"#![allow(clippy::unwrap_used)]
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_platform_config_dir_resolves() {
+    assert!(dirs::config_dir().is_some());
+}
+
+#[cfg(target_os = "macos")]
+#[test]
+fn test_macos_config_dir_is_library_application_support() {
+    let home = std::env::var("HOME").unwrap();
+    let expected = std::path::PathBuf::from(home)
+        .join("Library")
+        .join("Application Support");
+    assert_eq!(dirs::config_dir().unwrap(), expected);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_linux_config_dir_defaults_to_home_config() {
+    if std::env::var("XDG_CONFIG_HOME").is_err() {
+        let home = std::env::var("HOME").unwrap();
+        let expected = std::path::PathBuf::from(home).join(".config");
+        assert_eq!(dirs::config_dir().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_config_load_from_explicit_path() {
+    let tmpdir = TempDir::new().unwrap();
+    let config_file = tmpdir.path().join("test.{{EXTENSION}}");
+    let fixture = {{EXPLICIT_FIXTURE}};
+    fs::write(&config_file, fixture).unwrap();
+
+    let (config, _) = Config::load(Some(&config_file)).unwrap();
+    assert_eq!(config.name, "Test User");
+    assert_eq!(config.age, 42);
+    assert!(config.debug);
+}
+
+#[test]
+fn test_config_load_explicit_nonexistent_errors() {
+    let result = Config::load(Some(&std::path::PathBuf::from("/nonexistent/path.{{EXTENSION}}")));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_config_default_values() {
+    let config = Config::default();
+    assert_eq!(config.name, "John Doe");
+    assert_eq!(config.age, 30);
+    assert!(!config.debug);
+    assert_eq!(config.log.rotation, Rotation::Size);
+    assert_eq!(config.log.keep, 5);
+}
+
+#[test]
+fn test_config_load_log_section() {
+    let tmpdir = TempDir::new().unwrap();
+    let config_file = tmpdir.path().join("test.{{EXTENSION}}");
+    let fixture = {{LOG_FIXTURE}};
+    fs::write(&config_file, fixture).unwrap();
+
+    let (config, _) = Config::load(Some(&config_file)).unwrap();
+    assert_eq!(config.log.rotation, Rotation::Daily);
+    assert_eq!(config.log.keep, 2);
+    assert_eq!(config.log.max_size_mb, 10);
+}
+
+#[test]
+fn test_config_env_overrides_file_values() {
+    let mut config = Config::default();
+    let vars = [
+        (env_var_name("age"), "41".to_string()),
+        (env_var_name("log.max-size-mb"), "20".to_string()),
+        ("UNRELATED_AGE".to_string(), "99".to_string()),
+    ];
+
+    config.apply_env(vars).unwrap();
+    assert_eq!(config.age, 41);
+    assert_eq!(config.log.max_size_mb, 20);
+    assert_eq!(config.name, "John Doe");
+}
+
+#[test]
+fn test_config_env_rejects_invalid_values() {
+    let mut config = Config::default();
+    let error = config
+        .apply_env([(env_var_name("debug"), "maybe".to_string())])
+        .unwrap_err();
+    assert!(format!("{:?}", error).contains(&env_var_name("debug")));
+}
+
+#[test]
+fn test_config_set_overrides_win() {
+    let config = Config::default()
+        .with_overrides(&["name=Ops".to_string(), "log.rotation=daily".to_string()])
+        .unwrap();
+    assert_eq!(config.name, "Ops");
+    assert_eq!(config.log.rotation, Rotation::Daily);
+}
+
+#[test]
+fn test_config_set_rejects_unknown_keys_and_malformed_pairs() {
+    for set in ["nope=1", "age", "log.keep=-1"] {
+        let result = Config::default().with_overrides(&[set.to_string()]);
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn test_sample_config_matches_defaults() {
+    let tmpdir = TempDir::new().unwrap();
+    let config_file = tmpdir.path().join("sample.{{EXTENSION}}");
+    fs::write(&config_file, SAMPLE_CONFIG).unwrap();
+
+    let (sample, _) = Config::load(Some(&config_file)).unwrap();
+    let defaults = Config::default();
+    assert_eq!(sample.name, defaults.name);
+    assert_eq!(sample.age, defaults.age);
+    assert_eq!(sample.log.rotation, defaults.log.rotation);
+    assert_eq!(sample.log.keep, defaults.log.keep);
+    assert_eq!(sample.source, Some(config_file));
+}
+
+#[test]
+fn test_search_paths_end_with_local_fallback() {
+    let paths = Config::search_paths();
+    let fallback = format!("{}.{}", APP_NAME, CONFIG_EXTENSION);
+    assert_eq!(paths.last().unwrap(), &std::path::PathBuf::from(fallback));
+}
+
+#[test]
+fn test_config_validate_command() {
+    let tmpdir = TempDir::new().unwrap();
+    let valid = tmpdir.path().join("valid.{{EXTENSION}}");
+    fs::write(&valid, SAMPLE_CONFIG).unwrap();
+    let invalid = tmpdir.path().join("invalid.{{EXTENSION}}");
+    fs::write(&invalid, "[[[").unwrap();
+
+    let validate = |file: &std::path::Path| {
+        run_command(
+            &ConfigCommand::Validate {
+                file: file.to_path_buf(),
+            },
+            &Config::default(),
+        )
+    };
+    assert!(validate(&valid).is_ok());
+    assert!(validate(&invalid).is_err());
+}
+
+#[test]
+fn test_config_show_and_path_commands() {
+    assert!(run_command(&ConfigCommand::Show, &Config::default()).is_ok());
+    assert!(run_command(&ConfigCommand::Path, &Config::default()).is_ok());
+}
+
+fn rotating_file(dir: &TempDir, rotation: Rotation, keep: usize) -> RotatingFile {
+    let config = LogConfig {
+        rotation,
+        keep,
+        ..LogConfig::default()
+    };
+    let mut file = RotatingFile::open(&dir.path().join("app.log"), &config).unwrap();
+    file.max_size = 10;
+    file
+}
+
+#[test]
+fn test_rotating_file_rotates_by_size_and_keeps_newest() {
+    let tmpdir = TempDir::new().unwrap();
+    let mut file = rotating_file(&tmpdir, Rotation::Size, 2);
+
+    for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+        file.write_all(line.as_bytes()).unwrap();
+    }
+
+    let read = |name: &str| fs::read_to_string(tmpdir.path().join(name)).unwrap();
+    assert_eq!(read("app.log"), "fourth\n");
+    assert_eq!(read("app.log.1"), "third\n");
+    assert_eq!(read("app.log.2"), "second\n");
+    assert!(!tmpdir.path().join("app.log.3").exists());
+}
+
+#[test]
+fn test_rotating_file_rotates_on_new_day() {
+    let tmpdir = TempDir::new().unwrap();
+    let mut file = rotating_file(&tmpdir, Rotation::Daily, 1);
+
+    file.write_all(b"today\n").unwrap();
+    file.write_all(b"still today\n").unwrap();
+    assert!(!tmpdir.path().join("app.log.1").exists());
+
+    file.day -= 1;
+    file.write_all(b"tomorrow\n").unwrap();
+    assert_eq!(
+        fs::read_to_string(tmpdir.path().join("app.log.1")).unwrap(),
+        "today\nstill today\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmpdir.path().join("app.log")).unwrap(),
+        "tomorrow\n"
+    );
+}
+
+#[test]
+fn test_rotating_file_without_rotation_appends() {
+    let tmpdir = TempDir::new().unwrap();
+    let mut file = rotating_file(&tmpdir, Rotation::Never, 2);
+
+    file.write_all(b"a long first line\n").unwrap();
+    file.write_all(b"a long second line\n").unwrap();
+
+    let content = fs::read_to_string(tmpdir.path().join("app.log")).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    assert!(!tmpdir.path().join("app.log.1").exists());
+}
+"#;
+
+    let tests_rs = tests_rs
+        .replace("{{EXPLICIT_FIXTURE}}", explicit_fixture)
+        .replace("{{LOG_FIXTURE}}", log_fixture)
+        .replace("{{EXTENSION}}", format.extension());
+
+    writer.write_file(&tests_dir.join("tests.rs"), &tests_rs)?;
+
+    Ok(())
+}
+
+/// Contents of `<project>.<extension>`, written as the sample config and by the generated `config init`
+fn sample_config(project_name: &str, format: ConfigFormat) -> String {
+    let file_name = format!("{}.{}", project_name, format.extension());
+    match format {
+        ConfigFormat::Yaml => format!(
+            r#"# {} - Sample configuration file
+# YAML keys use kebab-case; serde translates to snake_case in Rust.
+name: John Doe
+age: 30
+debug: false
+# Log file rotation: size (at max-size-mb), daily or never, keeping the newest `keep` old files
+log:
+  rotation: size
+  max-size-mb: 10
+  keep: 5
+"#,
+            file_name
+        ),
+        ConfigFormat::Toml => format!(
+            r#"# {} - Sample configuration file
+# TOML keys use kebab-case; serde translates to snake_case in Rust.
+name = "John Doe"
+age = 30
+debug = false
+
+# Log file rotation: size (at max-size-mb), daily or never, keeping the newest `keep` old files
+[log]
+rotation = "size"
+max-size-mb = 10
+keep = 5
+"#,
+            file_name
+        ),
+        // JSON has no comments, so the file is only the values
+        ConfigFormat::Json => r#"{
+  "name": "John Doe",
+  "age": 30,
+  "debug": false,
+  "log": {
+    "rotation": "size",
+    "max-size-mb": 10,
+    "keep": 5
+  }
+}
+"#
+        .to_string(),
+    }
+}
+
+pub(super) fn generate_sample_config(
+    project_name: &str,
+    format: ConfigFormat,
+    target_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let file_name = format!("{}.{}", project_name, format.extension());
+    writer.write_file(&target_dir.join(file_name), &sample_config(project_name, format))?;
+
+    Ok(())
+}
+
+/// lib.rs of the `<name>-core` crate in a workspace-kind project, where the domain logic goes
+pub(super) fn generate_core_lib_rs(project_name: &str, src_dir: &Path, writer: &mut FileWriter) -> Result<()> {
+    let lib_rs = r#"//! Core logic of {{PROJECT}}, kept free of CLI concerns so other crates can reuse it
+
+/// Greeting shown by the {{PROJECT}} binary
+pub fn greeting(name: &str) -> String {
+    format!("Hello from {}!", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_greeting() {
+        assert_eq!(greeting("{{PROJECT}}"), "Hello from {{PROJECT}}!");
+    }
+}
+"#
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&src_dir.join("lib.rs"), &lib_rs)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_main_rs() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let project_name = "test-main";
+
+        let result = generate_main_rs(
+            project_name,
+            &src_dir,
+            None,
+            &[],
+            LoggingBackend::EnvLogger,
+            false,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        let main_rs = fs::read_to_string(src_dir.join("main.rs")).unwrap();
+
+        assert!(main_rs.contains("use clap::Parser"));
+        assert!(main_rs.contains("use colored::*"));
+        assert!(main_rs.contains("use eyre::{Context, Result}"));
+        assert!(main_rs.contains("fn main()"));
+        assert!(main_rs.contains("fn setup_logging(level: log::LevelFilter, config: &LogConfig)"));
+        assert!(main_rs.contains("setup_logging(cli.log_level(), &config.log)"));
+        assert!(main_rs.contains("config.with_overrides(&cli.set)"));
+        assert!(main_rs.contains("use config::{Config, LogConfig, RotatingFile};"));
+        assert!(main_rs.contains("let (config, warnings) = Config::load(cli.config.as_ref())"));
+        assert!(main_rs.contains("eprintln!(\"Warning: {}\", warning);"));
+        assert!(
+            main_rs.find("setup_logging(cli.log_level()").unwrap() < main_rs.find("for warning in &warnings").unwrap()
+        );
+        assert!(main_rs.contains("dirs::data_local_dir()"));
+        assert!(main_rs.contains("fn run_application"));
+        assert!(main_rs.contains(project_name));
+        assert!(main_rs.contains("mod cli"));
+        assert!(main_rs.contains("mod config"));
+        assert!(main_rs.contains("Command::Config(command) => crate::config::run_command(command, config),"));
+        assert!(!main_rs.contains("Command::Completions"));
+    }
+
+    #[test]
+    fn test_generate_main_rs_dispatches_completions() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_main_rs(
+            "test-main",
+            temp_dir.path(),
+            None,
+            &[],
+            LoggingBackend::EnvLogger,
+            true,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let main_rs = fs::read_to_string(temp_dir.path().join("main.rs")).unwrap();
+        assert!(main_rs.contains("crate::cli::print_completions(*shell);"));
+        assert!(main_rs.contains("Command::Man { dir } => crate::cli::write_man_pages(dir),"));
+    }
+
+    #[test]
+    fn test_generate_main_rs_wraps_library() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_main_rs(
+            "test-main",
+            temp_dir.path(),
+            Some("test-main"),
+            &[],
+            LoggingBackend::Tracing,
+            false,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let main_rs = fs::read_to_string(temp_dir.path().join("main.rs")).unwrap();
+        assert!(main_rs.contains("use test_main::{Cli, Config};"));
+        assert!(main_rs.contains("test_main::run(&cli, &config)"));
+        assert!(main_rs.contains("tracing_subscriber::fmt()"));
+        assert!(!main_rs.contains("env_logger"));
+        assert!(!main_rs.contains("fn run_application"));
+        assert!(!main_rs.contains("mod cli"));
+        assert!(!main_rs.contains("use colored::*"));
+    }
+
+    #[test]
+    fn test_generate_main_rs_with_fern_leaves_warnings_to_the_logger() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_main_rs(
+            "tool",
+            temp_dir.path(),
+            None,
+            &[],
+            LoggingBackend::Fern,
+            false,
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let main_rs = fs::read_to_string(temp_dir.path().join("main.rs")).unwrap();
+        assert!(main_rs.contains("for warning in &warnings {\n        warn!(\"{}\", warning);\n    }"));
+        assert!(!main_rs.contains("eprintln!"));
+    }
+
+    #[test]
+    fn test_generate_lib_rs() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_lib_rs("test-lib", temp_dir.path(), &[], false, &mut FileWriter::default()).unwrap();
+
+        let lib_rs = fs::read_to_string(temp_dir.path().join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub mod cli;"));
+        assert!(lib_rs.contains("pub mod config;"));
+        assert!(lib_rs.contains("pub fn run(cli: &Cli, config: &Config) -> Result<()>"));
+        assert!(lib_rs.contains("\"test-lib\".cyan()"));
+        assert!(lib_rs.contains("Cli::parse_from([\"test-lib\"])"));
+        assert!(!lib_rs.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_cli_rs() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let project_name = "test-cli";
+
+        let result = generate_cli_rs(project_name, &[], false, &src_dir, &mut FileWriter::default());
+        assert!(result.is_ok());
+
+        let cli_rs = fs::read_to_string(src_dir.join("cli.rs")).unwrap();
+
+        assert!(cli_rs.contains("use clap::{Parser, Subcommand};"));
+        assert!(cli_rs.contains("pub command: Option<Command>"));
+        assert!(cli_rs.contains("    Config(ConfigCommand),"));
+        assert!(cli_rs.contains("pub enum ConfigCommand"));
+        assert!(cli_rs.contains("#[derive(Parser)]"));
+        assert!(cli_rs.contains(&format!("name = \"{}\"", project_name)));
+        assert!(cli_rs.contains("pub struct Cli"));
+        assert!(cli_rs.contains("pub config: Option<PathBuf>"));
+        assert!(cli_rs.contains("action = clap::ArgAction::Count"));
+        assert!(cli_rs.contains("pub verbose: u8"));
+        assert!(cli_rs.contains("pub quiet: bool"));
+        assert!(cli_rs.contains("pub set: Vec<String>"));
+        assert!(cli_rs.contains("TEST_CLI_<KEY> environment variables"));
+        assert!(cli_rs.contains("pub fn log_level(&self) -> log::LevelFilter"));
+        assert!(cli_rs.contains("A CLI application generated by rust-scaffold"));
+        assert!(!cli_rs.contains("clap_complete"));
+    }
+
+    #[test]
+    fn test_generate_cli_rs_with_completions() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_cli_rs("tool", &[], true, temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let cli_rs = fs::read_to_string(temp_dir.path().join("cli.rs")).unwrap();
+        assert_eq!(cli_rs.matches("#[command(hide = true)]").count(), 2);
+        assert!(cli_rs.contains("shell: clap_complete::Shell,"));
+        assert!(cli_rs.contains("pub fn print_completions(shell: clap_complete::Shell)"));
+        assert!(cli_rs.contains("clap_mangen::generate_to(<Cli as clap::CommandFactory>::command(), dir)"));
+    }
+
+    #[test]
+    fn test_generate_config_rs() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let result = generate_config_rs(
+            "test-config",
+            ConfigFormat::Yaml,
+            false,
+            &src_dir,
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        let config_rs = fs::read_to_string(src_dir.join("config.rs")).unwrap();
+
+        assert!(config_rs.contains("use serde::{Deserialize, Serialize}"));
+        assert!(config_rs.contains("rename_all = \"kebab-case\""));
+        assert!(config_rs.contains("pub struct Config"));
+        assert!(config_rs.contains("pub name: String"));
+        assert!(config_rs.contains("pub age: u32"));
+        assert!(config_rs.contains("pub debug: bool"));
+        assert!(config_rs.contains("impl Default for Config"));
+        assert!(config_rs.contains("dirs::config_dir()"));
+        assert!(config_rs.contains("pub fn load"));
+        assert!(config_rs.contains("fn load_from_file"));
+        assert!(config_rs.contains("John Doe"));
+        assert!(config_rs.contains("#[cfg(test)]\nmod tests;"));
+        assert!(config_rs.contains("const APP_NAME: &str = \"test-config\";"));
+        assert!(config_rs.contains("const CONFIG_EXTENSION: &str = \"yml\";"));
+        assert!(config_rs.contains("const ENV_PREFIX: &str = \"TEST_CONFIG\";"));
+        assert!(config_rs.contains("pub fn with_overrides(mut self, sets: &[String]) -> Result<Self>"));
+        assert!(config_rs.contains("pub fn run_command(command: &ConfigCommand, config: &Config) -> Result<()>"));
+        assert!(config_rs.contains("const SAMPLE_CONFIG: &str = r##\"# test-config.yml - Sample configuration file"));
+        assert!(config_rs.contains("serde_yaml::to_string(config)"));
+        assert!(config_rs.contains("serde_yaml::from_str(&content)"));
+        assert!(!config_rs.contains("mod proptests;"));
+    }
+
+    #[test]
+    fn test_generate_config_rs_in_toml() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_config_rs(
+            "tool",
+            ConfigFormat::Toml,
+            true,
+            temp_dir.path(),
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let config_rs = fs::read_to_string(temp_dir.path().join("config.rs")).unwrap();
+        assert!(config_rs.contains("const CONFIG_EXTENSION: &str = \"toml\";"));
+        assert!(config_rs.contains("toml::from_str(&content)"));
+        assert!(config_rs.contains("// TOML keys use kebab-case"));
+        assert!(config_rs.contains("mod tests;\n\n#[cfg(test)]\nmod proptests;\n"));
+        assert!(!config_rs.contains("serde_yaml"));
+        assert!(!config_rs.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_config_tests_rs() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let result = generate_config_tests_rs(ConfigFormat::Yaml, &src_dir, &mut FileWriter::default());
+        assert!(result.is_ok());
+
+        let tests_rs = fs::read_to_string(src_dir.join("config/tests.rs")).unwrap();
+
+        assert!(tests_rs.contains("use super::*"));
+        assert!(tests_rs.contains("dirs::config_dir()"));
+        assert!(tests_rs.contains("target_os = \"macos\""));
+        assert!(tests_rs.contains("target_os = \"linux\""));
+        assert!(tests_rs.contains("XDG_CONFIG_HOME"));
+        assert!(tests_rs.contains("Application Support"));
+        assert!(tests_rs.contains("test_config_load_from_explicit_path"));
+        assert!(tests_rs.contains("test_config_default_values"));
+        assert!(tests_rs.contains("let fixture = \"name: Test User\\nage: 42\\ndebug: true\";"));
+    }
+
+    #[test]
+    fn test_generate_config_tests_rs_fixtures_follow_format() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_config_tests_rs(ConfigFormat::Json, temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let tests_rs = fs::read_to_string(temp_dir.path().join("config/tests.rs")).unwrap();
+        assert!(tests_rs.contains("tmpdir.path().join(\"test.json\")"));
+        assert!(tests_rs.contains(r##"r#"{"log": {"rotation": "daily", "keep": 2}}"#"##));
+        assert!(!tests_rs.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_sample_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_name = "test-sample";
+
+        let result = generate_sample_config(
+            project_name,
+            ConfigFormat::Yaml,
+            temp_dir.path(),
+            &mut FileWriter::default(),
+        );
+        assert!(result.is_ok());
+
+        let config_file = temp_dir.path().join(format!("{}.yml", project_name));
+        assert!(config_file.exists());
+
+        let config_content = fs::read_to_string(&config_file).unwrap();
+
+        assert!(config_content.contains(project_name));
+        assert!(config_content.contains("name: John Doe"));
+        assert!(config_content.contains("age: 30"));
+        assert!(config_content.contains("debug: false"));
+        assert!(config_content.contains("Sample configuration file"));
+    }
+
+    #[test]
+    fn test_generate_sample_config_in_toml_and_json() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_sample_config("tool", ConfigFormat::Toml, temp_dir.path(), &mut FileWriter::default()).unwrap();
+        generate_sample_config("tool", ConfigFormat::Json, temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let toml_content = fs::read_to_string(temp_dir.path().join("tool.toml")).unwrap();
+        let toml: toml_edit::DocumentMut = toml_content.parse().unwrap();
+        assert_eq!(toml["name"].as_str(), Some("John Doe"));
+        assert_eq!(toml["log"]["max-size-mb"].as_integer(), Some(10));
+
+        let json_content = fs::read_to_string(temp_dir.path().join("tool.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json_content).unwrap();
+        assert_eq!(json["age"], 30);
+        assert_eq!(json["log"]["rotation"], "size");
+        assert!(!temp_dir.path().join("tool.yml").exists());
+    }
+
+    #[test]
+    fn test_generate_core_lib_rs() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_core_lib_rs("tool", temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let lib_rs = fs::read_to_string(temp_dir.path().join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub fn greeting(name: &str) -> String"));
+        assert!(lib_rs.contains("Hello from tool!"));
+        assert!(lib_rs.contains("#[cfg(test)]"));
+    }
+}
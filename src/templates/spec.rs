@@ -0,0 +1,460 @@
+use super::FileWriter;
+use super::commands::{Subcommand, builtin_variants, generate_commands_mod};
+use super::source::{COMPLETIONS_FNS, CONFIG_ARGS, CONFIG_COMMAND, LOG_LEVEL_IMPL, VERBOSITY_ARGS, config_precedence};
+use crate::cli_spec::{ArgKind, ArgSpec, CliSpec, SPEC_FILE, identifier};
+use colored::*;
+use eyre::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Subcommands described by `spec`, each with the smallest invocation its required arguments allow
+pub(super) fn spec_subcommands(spec: &CliSpec) -> Result<Vec<Subcommand>> {
+    spec.commands
+        .iter()
+        .map(|command| {
+            let mut sample_args = Vec::new();
+            for arg in command.args.iter().filter(|arg| arg.is_required()) {
+                match arg.kind {
+                    ArgKind::Positional => sample_args.push(arg.value_type.sample().to_string()),
+                    ArgKind::Option => {
+                        sample_args.push(format!("--{}", arg.name));
+                        sample_args.push(arg.value_type.sample().to_string());
+                    }
+                    ArgKind::Flag => {}
+                }
+            }
+            Subcommand::new(&command.name, sample_args)
+        })
+        .collect()
+}
+
+/// cli.rs and its parser tests from `spec`, commands/mod.rs, and stubs for handlers that don't exist yet
+pub(super) fn generate_spec_cli(
+    project_name: &str,
+    spec: &CliSpec,
+    subcommands: &[Subcommand],
+    completions: bool,
+    src_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    generate_cli_rs(project_name, spec, subcommands, completions, src_dir, writer)?;
+    generate_cli_tests_rs(project_name, spec, subcommands, src_dir, writer)?;
+
+    let commands_dir = src_dir.join("commands");
+    writer.create_dir_all(&commands_dir)?;
+    generate_commands_mod(subcommands, completions, &commands_dir, writer)?;
+    generate_handler_stubs(project_name, subcommands, &commands_dir, writer)?;
+
+    Ok(())
+}
+
+fn generate_cli_rs(
+    project_name: &str,
+    spec: &CliSpec,
+    subcommands: &[Subcommand],
+    completions: bool,
+    src_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let mut root_fields = String::new();
+    for arg in &spec.args {
+        root_fields.push_str(&arg_field(arg)?);
+    }
+
+    let mut variants = String::new();
+    let mut args_structs = String::new();
+    for (command, subcommand) in spec.commands.iter().zip(subcommands) {
+        // Without a doc comment clap would describe the command with the args struct's
+        let about = command
+            .about
+            .as_deref()
+            .map(single_line)
+            .unwrap_or_else(|| format!("Run the {} subcommand", command.name));
+        variants.push_str(&format!("    /// {}\n", about));
+        variants.push_str(&format!("    {}({}Args),\n", subcommand.variant, subcommand.variant));
+
+        let mut fields = String::new();
+        for arg in &command.args {
+            fields.push_str(&arg_field(arg)?);
+        }
+        // Fields come with a leading blank line, which the struct's opening brace doesn't want
+        args_structs.push_str(&format!(
+            "\n/// Arguments for `{} {}`\n#[derive(Debug, Args)]\npub struct {}Args {{{}}}\n",
+            project_name,
+            command.name,
+            subcommand.variant,
+            fields
+                .strip_prefix('\n')
+                .map(|fields| format!("\n{}", fields))
+                .unwrap_or_default()
+        ));
+    }
+
+    let about = spec
+        .about
+        .as_deref()
+        .unwrap_or("A CLI application generated by rust-scaffold");
+
+    let cli_rs = r#"// Generated from {{SPEC_FILE}} by scaffold; edit the spec and run `scaffold regen-cli` instead
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(
+    name = "{{PROJECT}}",
+    about = {{ABOUT}},
+    version = env!("GIT_DESCRIBE"),
+    after_help = "{{CONFIG_PRECEDENCE}}",
+)]
+pub struct Cli {
+{{CONFIG_ARGS}}
+
+{{VERBOSITY_ARGS}}
+{{ROOT_FIELDS}}
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+{{LOG_LEVEL_IMPL}}
+#[derive(Subcommand)]
+pub enum Command {
+{{VARIANTS}}{{BUILTIN_VARIANTS}}}
+{{CONFIG_COMMAND}}{{ARGS_STRUCTS}}{{COMPLETIONS_FNS}}
+#[cfg(test)]
+mod tests;
+"#
+    .replace("{{SPEC_FILE}}", SPEC_FILE)
+    .replace("{{ABOUT}}", &format!("{:?}", single_line(about)))
+    .replace("{{CONFIG_ARGS}}", CONFIG_ARGS)
+    .replace("{{CONFIG_PRECEDENCE}}", &config_precedence(project_name))
+    .replace("{{VERBOSITY_ARGS}}", VERBOSITY_ARGS)
+    .replace("{{LOG_LEVEL_IMPL}}", LOG_LEVEL_IMPL)
+    .replace("{{ROOT_FIELDS}}", &root_fields)
+    .replace("{{VARIANTS}}", &variants)
+    .replace("{{BUILTIN_VARIANTS}}", &builtin_variants(completions))
+    .replace("{{CONFIG_COMMAND}}", CONFIG_COMMAND)
+    .replace("{{ARGS_STRUCTS}}", &args_structs)
+    .replace("{{COMPLETIONS_FNS}}", if completions { COMPLETIONS_FNS } else { "" })
+    .replace("{{PROJECT}}", project_name);
+
+    writer.write_file(&src_dir.join("cli.rs"), &cli_rs)
+}
+
+/// A struct field with its `#[arg]` attribute, preceded by a blank line
+fn arg_field(arg: &ArgSpec) -> Result<String> {
+    let field = identifier("argument", &arg.name)?;
+    let mut attributes = Vec::new();
+
+    if arg.kind != ArgKind::Positional {
+        if let Some(short) = arg.short {
+            attributes.push(format!("short = '{}'", short));
+        }
+        attributes.push("long".to_string());
+    }
+    if let Some(default) = &arg.default {
+        attributes.push(format!("default_value = {:?}", default));
+    }
+    if let Some(env) = &arg.env {
+        attributes.push(format!("env = {:?}", env));
+    }
+
+    let value_type = arg.value_type.rust_type();
+    let field_type = match arg.kind {
+        ArgKind::Flag => "bool".to_string(),
+        _ if arg.multiple => {
+            if arg.is_required() {
+                attributes.push("required = true".to_string());
+            }
+            format!("Vec<{}>", value_type)
+        }
+        _ if arg.is_required() || arg.default.is_some() => value_type.to_string(),
+        _ => format!("Option<{}>", value_type),
+    };
+
+    let mut lines = String::from("\n");
+    if let Some(help) = &arg.help {
+        let help = single_line(help);
+        lines.push_str(&format!("    /// {}\n", help));
+        attributes.push(format!("help = {:?}", help));
+    }
+    if !attributes.is_empty() {
+        lines.push_str(&format!("    #[arg({})]\n", attributes.join(", ")));
+    }
+    lines.push_str(&format!("    pub {}: {},\n", field, field_type));
+
+    Ok(lines)
+}
+
+/// src/cli/tests.rs asserting the parser accepts each command's minimal invocation and its examples
+fn generate_cli_tests_rs(
+    project_name: &str,
+    spec: &CliSpec,
+    subcommands: &[Subcommand],
+    src_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let tests_dir = src_dir.join("cli");
+    writer.create_dir_all(&tests_dir)?;
+
+    let parse_test = |name: String, args: Vec<&str>| {
+        let args: String = std::iter::once(project_name)
+            .chain(args)
+            .map(|arg| format!("{:?}", arg))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "\n#[test]\nfn {}() {{\n    let result = Cli::try_parse_from([{}]);\n    assert!(result.is_ok(), \"{{:?}}\", result.err());\n}}\n",
+            name, args
+        )
+    };
+
+    let mut tests = String::new();
+    for (command, subcommand) in spec.commands.iter().zip(subcommands) {
+        let minimal = std::iter::once(subcommand.name.as_str())
+            .chain(subcommand.sample_args.iter().map(String::as_str))
+            .collect();
+        tests.push_str(&parse_test(format!("test_parses_{}", subcommand.module), minimal));
+
+        for (index, example) in command.examples.iter().enumerate() {
+            tests.push_str(&parse_test(
+                format!("test_parses_{}_example_{}", subcommand.module, index + 1),
+                example.split_whitespace().collect(),
+            ));
+        }
+    }
+
+    let tests_rs = format!(
+        r#"// Generated from {} by scaffold; `scaffold regen-cli` rewrites this file
+use super::*;
+use clap::{{CommandFactory, Parser}};
+
+#[test]
+fn test_cli_definition_is_valid() {{
+    Cli::command().debug_assert();
+}}
+{}"#,
+        SPEC_FILE, tests
+    );
+
+    writer.write_file(&tests_dir.join("tests.rs"), &tests_rs)
+}
+
+/// A handler module per command that doesn't have one yet; existing handlers are never touched
+fn generate_handler_stubs(
+    project_name: &str,
+    subcommands: &[Subcommand],
+    commands_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    for subcommand in subcommands {
+        let path = commands_dir.join(format!("{}.rs", subcommand.module));
+        if path.exists() {
+            log::info!("Keeping existing handler {}", path.display());
+            continue;
+        }
+
+        let handler_rs = r#"use crate::cli::{{VARIANT}}Args;
+use crate::config::Config;
+use colored::*;
+use eyre::Result;
+use log::info;
+
+/// Handle `{{PROJECT}} {{NAME}}`
+pub fn run(args: &{{VARIANT}}Args, config: &Config) -> Result<()> {
+    info!("Running {{NAME}} with {:?}", args);
+
+    println!(
+        "{} {{NAME}} is not implemented yet (user: {})",
+        "⚠".yellow(),
+        config.name
+    );
+    Ok(())
+}
+"#
+        .replace("{{PROJECT}}", project_name)
+        .replace("{{NAME}}", &subcommand.name)
+        .replace("{{VARIANT}}", &subcommand.variant);
+
+        writer.write_file(&path, &handler_rs)?;
+        println!("{} Added handler stub: commands/{}.rs", "✓".green(), subcommand.module);
+    }
+
+    // Handlers of commands removed from the spec are no longer compiled; leave deleting them to the user
+    for entry in fs::read_dir(commands_dir).context(format!("Failed to read {}", commands_dir.display()))? {
+        let path = entry?.path();
+        let Some(module) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let is_handler = path.extension().is_some_and(|ext| ext == "rs") && module != "mod";
+        if is_handler && !subcommands.iter().any(|subcommand| subcommand.module == module) {
+            println!(
+                "{} commands/{}.rs is no longer in {}; delete it or add the command back",
+                "⚠".yellow(),
+                module,
+                SPEC_FILE
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The package holding the generated CLI: `dir` itself, or the single `crates/*-cli` of a workspace root
+pub(super) fn cli_package_dir(dir: &Path) -> Result<PathBuf> {
+    let manifest = dir.join("Cargo.toml");
+    let content = fs::read_to_string(&manifest).context(format!("Failed to read {}", manifest.display()))?;
+    let doc: toml_edit::DocumentMut = content
+        .parse()
+        .context(format!("Failed to parse {}", manifest.display()))?;
+    if doc.contains_key("package") {
+        return Ok(dir.to_path_buf());
+    }
+
+    let crates_dir = dir.join("crates");
+    let mut candidates: Vec<PathBuf> = fs::read_dir(&crates_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join("src/cli.rs").exists())
+        .collect();
+
+    match candidates.len() {
+        1 => Ok(candidates.remove(0)),
+        _ => Err(eyre::eyre!(
+            "{} has no [package] and no single CLI crate under crates/ (use --directory to pick one)",
+            manifest.display()
+        )),
+    }
+}
+
+/// Binary name of the package at `package_dir`: its first `[[bin]]`, else the package name
+pub(super) fn binary_name(package_dir: &Path) -> Result<String> {
+    let manifest = package_dir.join("Cargo.toml");
+    let content = fs::read_to_string(&manifest).context(format!("Failed to read {}", manifest.display()))?;
+    let doc: toml_edit::DocumentMut = content
+        .parse()
+        .context(format!("Failed to parse {}", manifest.display()))?;
+
+    doc.get("bin")
+        .and_then(|bins| bins.as_array_of_tables())
+        .and_then(|bins| bins.iter().next())
+        .and_then(|bin| bin.get("name"))
+        .or_else(|| doc.get("package").and_then(|package| package.get("name")))
+        .and_then(|name| name.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| eyre::eyre!("{} has no package name", manifest.display()))
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn spec(yaml: &str) -> CliSpec {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_spec_subcommands_build_minimal_invocations() {
+        let spec = spec(
+            "commands:\n  - name: add\n    args:\n      - name: widget\n        kind: positional\n      - name: count\n        type: u32\n        required: true\n      - name: force\n        kind: flag\n",
+        );
+
+        let subcommands = spec_subcommands(&spec).unwrap();
+
+        assert_eq!(subcommands[0].sample_args, ["example", "--count", "1"]);
+    }
+
+    #[test]
+    fn test_arg_field_types_and_attributes() {
+        let field = |yaml: &str| arg_field(&serde_yaml::from_str::<ArgSpec>(yaml).unwrap()).unwrap();
+
+        assert_eq!(
+            field("name: count\ntype: u32\nshort: n\ndefault: \"1\"\nenv: COUNT\nhelp: How many"),
+            "\n    /// How many\n    #[arg(short = 'n', long, default_value = \"1\", env = \"COUNT\", help = \"How many\")]\n    pub count: u32,\n"
+        );
+        assert!(field("name: out-dir\ntype: path").contains("pub out_dir: Option<PathBuf>,"));
+        assert!(
+            field("name: files\nkind: positional\nmultiple: true")
+                .contains("#[arg(required = true)]\n    pub files: Vec<String>,")
+        );
+        assert!(
+            field("name: target\nkind: positional\nrequired: false").ends_with("    pub target: Option<String>,\n")
+        );
+        assert!(field("name: force\nkind: flag").contains("#[arg(long)]\n    pub force: bool,"));
+    }
+
+    #[test]
+    fn test_generate_spec_cli_keeps_existing_handlers() {
+        let temp_dir = TempDir::new().unwrap();
+        let commands_dir = temp_dir.path().join("commands");
+        fs::create_dir_all(&commands_dir).unwrap();
+        fs::write(commands_dir.join("add.rs"), "// my handler").unwrap();
+        fs::write(commands_dir.join("old.rs"), "// removed from the spec").unwrap();
+
+        let spec = spec(
+            "about: Widgets\ncommands:\n  - name: add\n    about: Add one\n    examples: [add --dry-run]\n    args:\n      - name: dry-run\n        kind: flag\n  - name: list\n",
+        );
+        let subcommands = spec_subcommands(&spec).unwrap();
+
+        generate_spec_cli(
+            "tool",
+            &spec,
+            &subcommands,
+            false,
+            temp_dir.path(),
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(commands_dir.join("add.rs")).unwrap(),
+            "// my handler"
+        );
+        let list_rs = fs::read_to_string(commands_dir.join("list.rs")).unwrap();
+        assert!(list_rs.contains("pub fn run(args: &ListArgs, config: &Config) -> Result<()>"));
+
+        let cli_rs = fs::read_to_string(temp_dir.path().join("cli.rs")).unwrap();
+        assert!(cli_rs.contains("about = \"Widgets\","));
+        assert!(
+            cli_rs
+                .contains("    /// Add one\n    Add(AddArgs),\n    /// Run the list subcommand\n    List(ListArgs),\n")
+        );
+        assert!(cli_rs.contains("pub struct AddArgs {\n    #[arg(long)]\n    pub dry_run: bool,\n}"));
+        assert!(cli_rs.contains("pub struct ListArgs {}"));
+
+        let tests_rs = fs::read_to_string(temp_dir.path().join("cli/tests.rs")).unwrap();
+        assert!(
+            tests_rs.contains("fn test_parses_list() {\n    let result = Cli::try_parse_from([\"tool\", \"list\"]);")
+        );
+        assert!(tests_rs.contains("Cli::try_parse_from([\"tool\", \"add\", \"--dry-run\"])"));
+    }
+
+    #[test]
+    fn test_cli_package_dir_and_binary_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let cli_dir = temp_dir.path().join("crates/tool-cli");
+        fs::create_dir_all(cli_dir.join("src")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("crates/tool-core/src")).unwrap();
+        fs::write(cli_dir.join("src/cli.rs"), "").unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            cli_dir.join("Cargo.toml"),
+            "[package]\nname = \"tool-cli\"\n\n[[bin]]\nname = \"tool\"\npath = \"src/main.rs\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(cli_package_dir(temp_dir.path()).unwrap(), cli_dir);
+        assert_eq!(cli_package_dir(&cli_dir).unwrap(), cli_dir);
+        assert_eq!(binary_name(&cli_dir).unwrap(), "tool");
+    }
+}
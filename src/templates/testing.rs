@@ -0,0 +1,171 @@
+use super::FileWriter;
+use super::source::format_parser;
+use crate::config::ConfigFormat;
+use eyre::Result;
+use std::path::Path;
+
+/// src/config/proptests.rs: any Config survives serializing and parsing back
+pub(super) fn generate_proptests(format: ConfigFormat, src_dir: &Path, writer: &mut FileWriter) -> Result<()> {
+    let (format_name, parser, serialize) = format_parser(format);
+
+    let tests_dir = src_dir.join("config");
+    writer.create_dir_all(&tests_dir)?;
+
+    let proptests_rs = r#"//! Property tests: every Config survives a round trip through {{FORMAT}}
+#![allow(clippy::unwrap_used)]
+use super::*;
+use proptest::prelude::*;
+
+fn rotation() -> impl Strategy<Value = Rotation> {
+    prop_oneof![
+        Just(Rotation::Size),
+        Just(Rotation::Daily),
+        Just(Rotation::Never)
+    ]
+}
+
+fn log_config() -> impl Strategy<Value = LogConfig> {
+    // TOML integers are i64, so sizes and counts stay within it
+    (rotation(), 0..=i64::MAX as u64, 0..=i64::MAX as usize).prop_map(
+        |(rotation, max_size_mb, keep)| LogConfig {
+            rotation,
+            max_size_mb,
+            keep,
+        },
+    )
+}
+
+fn config() -> impl Strategy<Value = Config> {
+    ("\\PC*", any::<u32>(), any::<bool>(), log_config()).prop_map(|(name, age, debug, log)| {
+        Config {
+            name,
+            age,
+            debug,
+            log,
+            source: None,
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_config_round_trips(config in config()) {
+        let text = {{PARSER}}::{{SERIALIZE}}(&config).unwrap();
+        let parsed: Config = {{PARSER}}::from_str(&text).unwrap();
+        prop_assert_eq!(parsed, config);
+    }
+}
+"#
+    .replace("{{FORMAT}}", format_name)
+    .replace("{{PARSER}}", parser)
+    .replace("{{SERIALIZE}}", serialize);
+
+    writer.write_file(&tests_dir.join("proptests.rs"), &proptests_rs)
+}
+
+/// fuzz/, a cargo-fuzz crate whose parse_config target feeds arbitrary input to the config parser
+///
+/// The input is parsed into the Config exported by `lib`, the package name of the project's lib target.
+pub(super) fn generate_fuzz(
+    project_name: &str,
+    format: ConfigFormat,
+    lib: &str,
+    package_dir: &Path,
+    writer: &mut FileWriter,
+) -> Result<()> {
+    let (_, parser, _) = format_parser(format);
+
+    let fuzz_dir = package_dir.join("fuzz");
+    let targets_dir = fuzz_dir.join("fuzz_targets");
+    writer.create_dir_all(&targets_dir)?;
+
+    let cargo_toml = r#"[package]
+name = "{{PROJECT}}-fuzz"
+version = "0.0.0"
+publish = false
+edition = "2024"
+
+[package.metadata]
+cargo-fuzz = true
+
+[dependencies]
+libfuzzer-sys = "*"
+{{PARSER}} = "*"
+{{LIB}} = { path = ".." }
+
+# Keep the fuzz crate out of the project's own workspace
+[workspace]
+members = ["."]
+
+[[bin]]
+name = "parse_config"
+path = "fuzz_targets/parse_config.rs"
+test = false
+doc = false
+bench = false
+"#
+    .replace("{{LIB}}", lib)
+    .replace("{{PARSER}}", parser)
+    .replace("{{PROJECT}}", project_name);
+    writer.write_file(&fuzz_dir.join("Cargo.toml"), &cargo_toml)?;
+
+    writer.write_file(&fuzz_dir.join(".gitignore"), "target\ncorpus\nartifacts\ncoverage\n")?;
+
+    let parse_config_rs = r#"#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Any input may be rejected, but parsing must never panic
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = {{PARSER}}::from_str::<{{CRATE}}::Config>(text);
+    }
+});
+"#
+    .replace("{{PARSER}}", parser)
+    .replace("{{CRATE}}", &lib.replace('-', "_"));
+    writer.write_file(&targets_dir.join("parse_config.rs"), &parse_config_rs)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_proptests() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_proptests(ConfigFormat::Json, temp_dir.path(), &mut FileWriter::default()).unwrap();
+
+        let proptests_rs = fs::read_to_string(temp_dir.path().join("config/proptests.rs")).unwrap();
+        assert!(proptests_rs.contains("serde_json::to_string_pretty(&config).unwrap()"));
+        assert!(proptests_rs.contains("let parsed: Config = serde_json::from_str(&text).unwrap();"));
+        assert!(proptests_rs.contains("prop_assert_eq!(parsed, config);"));
+        assert!(!proptests_rs.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_fuzz_parses_config() {
+        let temp_dir = TempDir::new().unwrap();
+
+        generate_fuzz(
+            "tool",
+            ConfigFormat::Toml,
+            "tool-cli",
+            temp_dir.path(),
+            &mut FileWriter::default(),
+        )
+        .unwrap();
+
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("fuzz/Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"tool-fuzz\""));
+        assert!(cargo_toml.contains("toml = \"*\"\ntool-cli = { path = \"..\" }"));
+        assert!(cargo_toml.contains("[workspace]\nmembers = [\".\"]"));
+        let target = fs::read_to_string(temp_dir.path().join("fuzz/fuzz_targets/parse_config.rs")).unwrap();
+        assert!(target.contains("toml::from_str::<tool_cli::Config>(text)"));
+        assert!(temp_dir.path().join("fuzz/.gitignore").exists());
+    }
+}
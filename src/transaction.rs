@@ -0,0 +1,268 @@
+use colored::*;
+use eyre::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static WRITING: AtomicBool = AtomicBool::new(false);
+
+/// Handle Ctrl-C: exit right away until the write phase starts, then turn it into an error at the
+/// next checkpoint so partial output gets rolled back
+pub fn install_interrupt_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if !WRITING.load(Ordering::SeqCst) {
+            // Nothing written yet, so nothing to roll back; don't wait for a prompt's Enter
+            std::process::exit(130);
+        }
+        INTERRUPTED.store(true, Ordering::SeqCst);
+        eprintln!("\nInterrupted, rolling back after the current step (press Enter if a prompt is waiting)");
+    })
+    .context("Failed to install Ctrl-C handler")
+}
+
+/// Mark the start of the write phase; from here Ctrl-C rolls back instead of exiting
+pub fn begin_writes() {
+    WRITING.store(true, Ordering::SeqCst);
+}
+
+/// Fail if Ctrl-C was pressed since the handler was installed
+pub fn check_interrupted() -> Result<()> {
+    if INTERRUPTED.load(Ordering::SeqCst) {
+        return Err(eyre::eyre!("Interrupted"));
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+enum Entry {
+    Created(PathBuf),
+    Overwritten { path: PathBuf, original: Vec<u8> },
+}
+
+/// Record of files and directories changed in an existing directory, so they can be restored
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: Vec<Entry>,
+    seen: HashSet<PathBuf>,
+}
+
+impl Journal {
+    /// Record a path before it is written: new paths are removed on rollback, existing files restored
+    pub fn record(&mut self, path: &Path) -> Result<()> {
+        if !self.seen.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+
+        if path.is_file() {
+            let original = fs::read(path).context(format!("Failed to back up {}", path.display()))?;
+            self.entries.push(Entry::Overwritten {
+                path: path.to_path_buf(),
+                original,
+            });
+        } else if !path.exists() {
+            self.entries.push(Entry::Created(path.to_path_buf()));
+        }
+
+        Ok(())
+    }
+
+    /// Record a directory before `create_dir_all`, tracking the topmost directory it would create
+    pub fn record_dir(&mut self, path: &Path) -> Result<()> {
+        match path.ancestors().take_while(|dir| !dir.exists()).last() {
+            Some(topmost) => self.record(topmost),
+            None => Ok(()),
+        }
+    }
+
+    /// Undo every recorded change, newest first; keeps going past failures and reports them
+    ///
+    /// Returns the number of changes undone.
+    pub fn rollback(self) -> Result<usize> {
+        let count = self.entries.len();
+        let mut failures = Vec::new();
+
+        for entry in self.entries.into_iter().rev() {
+            let result = match &entry {
+                Entry::Created(path) if path.is_dir() => fs::remove_dir_all(path),
+                Entry::Created(path) if path.exists() => fs::remove_file(path),
+                Entry::Created(_) => Ok(()),
+                Entry::Overwritten { path, original } => fs::write(path, original),
+            };
+
+            if let Err(e) = result {
+                let path = match &entry {
+                    Entry::Created(path) | Entry::Overwritten { path, .. } => path,
+                };
+                log::warn!("Failed to roll back {}: {}", path.display(), e);
+                failures.push(path.display().to_string());
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(eyre::eyre!("Failed to roll back: {}", failures.join(", ")));
+        }
+        Ok(count)
+    }
+}
+
+/// Sibling directory a new project is generated in before being renamed into place
+///
+/// Dropping it without `commit` removes the staging directory and any parents it created.
+pub struct StagingDir {
+    path: PathBuf,
+    target: PathBuf,
+    created_parent: Option<PathBuf>,
+    committed: bool,
+}
+
+impl StagingDir {
+    pub fn new(target: &Path) -> Result<Self> {
+        let target = std::path::absolute(target).context(format!("Failed to resolve {}", target.display()))?;
+        let parent = target
+            .parent()
+            .ok_or_else(|| eyre::eyre!("{} has no parent directory", target.display()))?;
+        let name = target
+            .file_name()
+            .ok_or_else(|| eyre::eyre!("{} has no directory name", target.display()))?;
+
+        let created_parent = parent
+            .ancestors()
+            .take_while(|dir| !dir.exists())
+            .last()
+            .map(Path::to_path_buf);
+        fs::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+
+        let path = parent.join(format!(".{}.scaffold-{}", name.to_string_lossy(), std::process::id()));
+        let staging = Self {
+            path,
+            target,
+            created_parent,
+            committed: false,
+        };
+        fs::create_dir(&staging.path).context(format!("Failed to create {}", staging.path.display()))?;
+
+        log::info!("Staging project in {}", staging.path.display());
+        Ok(staging)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Move the staged project into place
+    pub fn commit(mut self) -> Result<()> {
+        fs::rename(&self.path, &self.target).context(format!(
+            "Failed to move {} to {}",
+            self.path.display(),
+            self.target.display()
+        ))?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+
+        let cleanup = self.created_parent.as_ref().unwrap_or(&self.path);
+        match fs::remove_dir_all(cleanup) {
+            Ok(()) => println!("{} Removed partially generated project", "↩".yellow()),
+            Err(e) => log::warn!("Failed to remove {}: {}", cleanup.display(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_journal_rollback_removes_created_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("new.txt");
+        let mut journal = Journal::default();
+
+        journal.record(&file).unwrap();
+        fs::write(&file, "generated").unwrap();
+        journal.rollback().unwrap();
+
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_journal_rollback_restores_overwritten_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("README.md");
+        fs::write(&file, "original").unwrap();
+        let mut journal = Journal::default();
+
+        journal.record(&file).unwrap();
+        fs::write(&file, "generated").unwrap();
+        // Recording again must keep the original backup
+        journal.record(&file).unwrap();
+        fs::write(&file, "generated again").unwrap();
+        journal.rollback().unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "original");
+    }
+
+    #[test]
+    fn test_journal_record_dir_tracks_topmost_new_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join(".github").join("workflows");
+        let mut journal = Journal::default();
+
+        journal.record_dir(&nested).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("ci.yml"), "name: CI").unwrap();
+        journal.rollback().unwrap();
+
+        assert!(!temp_dir.path().join(".github").exists());
+        assert!(temp_dir.path().exists());
+    }
+
+    #[test]
+    fn test_journal_ignores_existing_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut journal = Journal::default();
+
+        journal.record_dir(temp_dir.path()).unwrap();
+        journal.record(temp_dir.path()).unwrap();
+
+        assert_eq!(journal.rollback().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_staging_dir_commit_moves_into_place() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("project");
+
+        let staging = StagingDir::new(&target).unwrap();
+        fs::write(staging.path().join("Cargo.toml"), "[package]").unwrap();
+        staging.commit().unwrap();
+
+        assert!(target.join("Cargo.toml").exists());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_staging_dir_drop_removes_staging_and_created_parents() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("a").join("b").join("project");
+
+        {
+            let staging = StagingDir::new(&target).unwrap();
+            fs::write(staging.path().join("Cargo.toml"), "[package]").unwrap();
+        }
+
+        assert!(!temp_dir.path().join("a").exists());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,354 @@
+use eyre::{Context, Result};
+use globset::GlobBuilder;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A cargo workspace enclosing the directory being scaffolded
+#[derive(Debug)]
+pub struct Workspace {
+    pub root: PathBuf,
+    dependencies: HashSet<String>,
+}
+
+impl Workspace {
+    /// Find the workspace whose root Cargo.toml sits above `target_dir`, the way cargo looks for it
+    ///
+    /// Returns None when there is none or when the workspace excludes `target_dir`.
+    pub fn find_enclosing(target_dir: &Path) -> Result<Option<Self>> {
+        let target = normalize(target_dir)?;
+
+        for dir in target.ancestors().skip(1) {
+            let manifest = dir.join("Cargo.toml");
+            if !manifest.is_file() {
+                continue;
+            }
+
+            let doc = read_manifest(&manifest)?;
+            let Some(workspace) = doc.get("workspace").and_then(|item| item.as_table_like()) else {
+                continue;
+            };
+
+            let member = relative_member(dir, &target);
+            if path_list(workspace.get("exclude"))
+                .iter()
+                .any(|excluded| member == *excluded || member.starts_with(&format!("{}/", excluded)))
+            {
+                log::info!("{} is excluded from the workspace at {}", member, dir.display());
+                return Ok(None);
+            }
+
+            let dependencies = workspace
+                .get("dependencies")
+                .and_then(|item| item.as_table_like())
+                .map(|deps| deps.iter().map(|(name, _)| name.to_string()).collect())
+                .unwrap_or_default();
+
+            return Ok(Some(Self {
+                root: dir.to_path_buf(),
+                dependencies,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    pub fn manifest(&self) -> PathBuf {
+        self.root.join("Cargo.toml")
+    }
+
+    /// Whether `[workspace.dependencies]` declares `name`, so members can use `workspace = true`
+    pub fn declares(&self, name: &str) -> bool {
+        self.dependencies.contains(name)
+    }
+
+    /// Path from `member_dir` back up to the `.git` at the workspace root, for the member's build.rs
+    pub fn git_dir_from(&self, member_dir: &Path) -> Result<String> {
+        let member = relative_member(&self.root, &normalize(member_dir)?);
+        Ok("../".repeat(member.split('/').filter(|part| !part.is_empty()).count()) + ".git")
+    }
+
+    /// Add `member_dir` to `[workspace] members` unless a member entry or glob already covers it
+    ///
+    /// Returns false when nothing had to change.
+    pub fn add_member(&self, member_dir: &Path) -> Result<bool> {
+        let manifest = self.manifest();
+        let member = relative_member(&self.root, &normalize(member_dir)?);
+        let mut doc = read_manifest(&manifest)?;
+
+        let workspace = doc
+            .get_mut("workspace")
+            .and_then(|item| item.as_table_like_mut())
+            .ok_or_else(|| eyre::eyre!("{} no longer has a [workspace] table", manifest.display()))?;
+
+        let members = path_list(workspace.get("members"));
+        let covered = members.iter().any(|pattern| {
+            GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map(|glob| glob.compile_matcher().is_match(&member))
+                .unwrap_or(false)
+        });
+        if covered {
+            log::info!("{} is already a member of {}", member, manifest.display());
+            return Ok(false);
+        }
+
+        match workspace.get_mut("members").and_then(|item| item.as_array_mut()) {
+            Some(array) => array.push(member.as_str()),
+            None => {
+                let mut array = toml_edit::Array::new();
+                array.push(member.as_str());
+                workspace.insert("members", toml_edit::value(array));
+            }
+        }
+
+        fs::write(&manifest, doc.to_string()).context(format!("Failed to write {}", manifest.display()))?;
+        log::info!("Added {} to the members of {}", member, manifest.display());
+        Ok(true)
+    }
+}
+
+/// Replace wildcard versions in `[workspace.dependencies]` of the manifest at `root` with what
+/// Cargo.lock resolved them to, the way `cargo add` would have written them
+///
+/// Returns how many dependencies were pinned.
+pub fn pin_dependencies(root: &Path) -> Result<usize> {
+    let manifest = root.join("Cargo.toml");
+    let lock = read_manifest(&root.join("Cargo.lock"))?;
+    let mut resolved: HashMap<&str, &str> = HashMap::new();
+    for package in lock
+        .get("package")
+        .and_then(|item| item.as_array_of_tables())
+        .into_iter()
+        .flatten()
+    {
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|item| item.as_str()),
+            package.get("version").and_then(|item| item.as_str()),
+        ) else {
+            continue;
+        };
+        let newer = resolved
+            .get(name)
+            .is_none_or(|current| version_key(version) > version_key(current));
+        if newer {
+            resolved.insert(name, version);
+        }
+    }
+
+    let mut doc = read_manifest(&manifest)?;
+    let Some(dependencies) = doc
+        .get_mut("workspace")
+        .and_then(|item| item.get_mut("dependencies"))
+        .and_then(|item| item.as_table_like_mut())
+    else {
+        return Ok(0);
+    };
+
+    let mut pinned = 0;
+    for (name, item) in dependencies.iter_mut() {
+        let Some(version) = resolved.get(name.get()) else {
+            continue;
+        };
+        let wildcard = match item.as_table_like_mut() {
+            Some(table) => table.get_mut("version"),
+            None => Some(item),
+        };
+        if let Some(wildcard) = wildcard.filter(|value| value.as_str() == Some("*")) {
+            // Build metadata is ignored in requirements and cargo warns about it
+            *wildcard = toml_edit::value(version.split('+').next().unwrap_or(version));
+            pinned += 1;
+        }
+    }
+
+    fs::write(&manifest, doc.to_string()).context(format!("Failed to write {}", manifest.display()))?;
+    log::info!("Pinned {} dependencies in {}", pinned, manifest.display());
+    Ok(pinned)
+}
+
+/// Numeric `major.minor.patch` for comparing lock file versions; pre-release tags are ignored
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn read_manifest(path: &Path) -> Result<toml_edit::DocumentMut> {
+    let content = fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+    content.parse().context(format!("Failed to parse {}", path.display()))
+}
+
+fn path_list(item: Option<&toml_edit::Item>) -> Vec<String> {
+    item.and_then(|item| item.as_array())
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|value| value.as_str())
+                .map(|path| path.trim_end_matches('/').to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `member` relative to the workspace root, with forward slashes as cargo writes it
+fn relative_member(root: &Path, member: &Path) -> String {
+    let relative = member.strip_prefix(root).unwrap_or(member);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Absolute path with `.` and `..` resolved lexically; the directory may not exist yet
+fn normalize(path: &Path) -> Result<PathBuf> {
+    let absolute = std::path::absolute(path).context(format!("Failed to resolve {}", path.display()))?;
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn workspace_with(manifest: &str) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_find_enclosing_returns_none_outside_a_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), "[package]\nname = \"solo\"\n").unwrap();
+
+        let workspace = Workspace::find_enclosing(&temp_dir.path().join("new-crate")).unwrap();
+
+        assert!(workspace.is_none());
+    }
+
+    #[test]
+    fn test_find_enclosing_reads_workspace_dependencies() {
+        let temp_dir = workspace_with("[workspace]\nmembers = []\n\n[workspace.dependencies]\nserde = \"1\"\n");
+
+        let workspace = Workspace::find_enclosing(&temp_dir.path().join("crates").join("new-crate"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(workspace.root, temp_dir.path());
+        assert!(workspace.declares("serde"));
+        assert!(!workspace.declares("clap"));
+    }
+
+    #[test]
+    fn test_find_enclosing_respects_exclude() {
+        let temp_dir = workspace_with("[workspace]\nexclude = [\"scratch/\"]\n");
+
+        let workspace = Workspace::find_enclosing(&temp_dir.path().join("scratch").join("tool")).unwrap();
+
+        assert!(workspace.is_none());
+    }
+
+    #[test]
+    fn test_add_member_appends_and_keeps_formatting() {
+        let temp_dir = workspace_with("# root\n[workspace]\nmembers = [\"core\"]  # keep\nresolver = \"2\"\n");
+        let workspace = Workspace::find_enclosing(&temp_dir.path().join("tool"))
+            .unwrap()
+            .unwrap();
+
+        assert!(workspace.add_member(&temp_dir.path().join("tool")).unwrap());
+
+        let manifest = fs::read_to_string(workspace.manifest()).unwrap();
+        assert!(manifest.starts_with("# root\n"));
+        assert!(manifest.contains("members = [\"core\", \"tool\"]  # keep"));
+    }
+
+    #[test]
+    fn test_add_member_creates_members_array() {
+        let temp_dir = workspace_with("[workspace]\n");
+        let member = temp_dir.path().join("crates").join("tool");
+        let workspace = Workspace::find_enclosing(&member).unwrap().unwrap();
+
+        assert!(workspace.add_member(&member).unwrap());
+
+        let manifest = fs::read_to_string(workspace.manifest()).unwrap();
+        assert!(manifest.contains("members = [\"crates/tool\"]"));
+    }
+
+    #[test]
+    fn test_add_member_skips_paths_covered_by_glob() {
+        let temp_dir = workspace_with("[workspace]\nmembers = [\"crates/*\"]\n");
+        let member = temp_dir.path().join("crates").join("tool");
+        let workspace = Workspace::find_enclosing(&member).unwrap().unwrap();
+
+        assert!(!workspace.add_member(&member).unwrap());
+    }
+
+    #[test]
+    fn test_add_member_glob_does_not_cover_nested_paths() {
+        let temp_dir = workspace_with("[workspace]\nmembers = [\"crates/*\"]\n");
+        let member = temp_dir.path().join("crates").join("tools").join("tool");
+        let workspace = Workspace::find_enclosing(&member).unwrap().unwrap();
+
+        assert!(workspace.add_member(&member).unwrap());
+
+        let manifest = fs::read_to_string(workspace.manifest()).unwrap();
+        assert!(manifest.contains("members = [\"crates/*\", \"crates/tools/tool\"]"));
+    }
+
+    #[test]
+    fn test_git_dir_from_member_points_at_workspace_root() {
+        let temp_dir = workspace_with("[workspace]\n");
+        let member = temp_dir.path().join("crates").join("tool");
+        let workspace = Workspace::find_enclosing(&member).unwrap().unwrap();
+
+        assert_eq!(workspace.git_dir_from(&member).unwrap(), "../../.git");
+        assert_eq!(
+            workspace.git_dir_from(&temp_dir.path().join("tool")).unwrap(),
+            "../.git"
+        );
+    }
+
+    #[test]
+    fn test_normalize_resolves_dot_segments() {
+        let normalized = normalize(Path::new("/a/b/../c/./d")).unwrap();
+        assert_eq!(normalized, PathBuf::from("/a/c/d"));
+    }
+
+    #[test]
+    fn test_pin_dependencies_uses_highest_locked_version() {
+        let temp_dir = workspace_with(
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\ntool-core = { path = \"crates/tool-core\" }\nclap = { version = \"*\", features = [\"derive\"] }\neyre = \"*\"\nserde = \"1\"\nserde_yaml = \"*\"\n",
+        );
+        fs::write(
+            temp_dir.path().join("Cargo.lock"),
+            "version = 4\n\n[[package]]\nname = \"clap\"\nversion = \"4.5.9\"\n\n[[package]]\nname = \"clap\"\nversion = \"4.5.20\"\n\n[[package]]\nname = \"eyre\"\nversion = \"0.6.12\"\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.210\"\n\n[[package]]\nname = \"serde_yaml\"\nversion = \"0.9.34+deprecated\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(pin_dependencies(temp_dir.path()).unwrap(), 3);
+
+        let manifest = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("clap = { version = \"4.5.20\", features = [\"derive\"] }"));
+        assert!(manifest.contains("eyre = \"0.6.12\""));
+        assert!(manifest.contains("serde = \"1\""));
+        assert!(manifest.contains("serde_yaml = \"0.9.34\""));
+        assert!(manifest.contains("tool-core = { path = \"crates/tool-core\" }"));
+    }
+}